@@ -14,24 +14,174 @@ pub enum UploadResult {
     Err(UploadError),
 }
 
-#[derive(Deserialize, Error, Debug)]
+#[derive(Deserialize, Debug)]
 #[serde(untagged)]
 pub enum UploadError {
-    #[error("multiple remote rejection: {errors}")]
     Aggregate {
-        #[from]
         errors: InnerAggregateError,
     },
-    #[error("remote error: {error}")]
     Single {
-        #[from]
         error: InnerError,
     },
-    #[error("unable to obtain CSRF token")]
+    /// JSONとして解釈できない応答
+    #[serde(skip)]
+    Status {
+        status: u16,
+        body: String,
+    },
     #[serde(skip)]
     UnableToObtainCsrfToken,
 }
 
+impl UploadError {
+    pub fn rejections(&self) -> Vec<Rejection> {
+        match self {
+            Self::Aggregate { errors } => errors.downloadable.as_ref()
+                .map(|d| d.file.0.iter().map(|raw| Rejection::classify(raw)).collect())
+                .unwrap_or_default(),
+            Self::Single { error } => vec![Rejection::classify(&error.0)],
+            Self::Status { status, body } => {
+                let reason = match status {
+                    // X-CSRF-Tokenが欠けているか古い
+                    422 => RejectionReason::CsrfInvalid,
+                    401 | 403 => RejectionReason::Unauthorized,
+                    404 => RejectionReason::NotFound,
+                    413 => RejectionReason::TooLarge,
+                    500..=599 => RejectionReason::ServerError,
+                    // HTMLのページはナビゲーションに「ログイン」などを含むので、JSONのときだけ中身を見る
+                    _ if serde_json::from_str::<serde_json::Value>(body).is_ok() => RejectionReason::classify(body),
+                    _ => RejectionReason::Unknown,
+                };
+                let excerpt = excerpt(body);
+                let raw = if excerpt.is_empty() { format!("HTTP {status}") } else { format!("HTTP {status}: {excerpt}") };
+                vec![Rejection { reason, raw }]
+            }
+            Self::UnableToObtainCsrfToken => vec![Rejection {
+                reason: RejectionReason::CsrfUnavailable,
+                raw: "unable to obtain CSRF token".to_string(),
+            }],
+        }
+    }
+}
+
+/// 応答の本文の先頭。HTMLならタイトルを使う
+fn excerpt(body: &str) -> String {
+    const MAX_CHARS: usize = 200;
+
+    let document = select::document::Document::from(body);
+    let title = document.find(select::predicate::Name("title")).next().map(|x| x.text());
+    let text = title.as_deref().unwrap_or(body).split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_CHARS {
+        format!("{}…", text.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
+
+impl Display for UploadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rejections = self.rejections();
+        if rejections.is_empty() {
+            return write!(f, "{self:?}")
+        }
+
+        for (i, rejection) in rejections.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{rejection}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+/// BOOTHがファイルを受け付けなかった理由
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RejectionReason {
+    EmptyFile,
+    UnsupportedFormat,
+    TooLarge,
+    TooSmall,
+    Unauthorized,
+    QuotaExceeded,
+    CsrfInvalid,
+    /// 編集ページにCSRFトークンが含まれていなかった
+    CsrfUnavailable,
+    NotFound,
+    /// 5xx
+    ServerError,
+    Unknown,
+}
+
+impl RejectionReason {
+    /// サーバーが返すメッセージは`Accept-Language`によって日本語か英語になるので、両方を見る。
+    /// 先に並べたものほど優先される。
+    const PATTERNS: [(Self, &'static [&'static str]); 7] = [
+        (Self::CsrfInvalid, &["csrf", "authenticity token", "不正なリクエスト"]),
+        (Self::Unauthorized, &["sign in", "log in", "login", "unauthorized", "not authorized", "permission", "ログイン", "権限"]),
+        (Self::QuotaExceeded, &["quota", "capacity", "disk space", "storage", "容量"]),
+        (Self::EmptyFile, &["empty", "blank", "空です", "空の"]),
+        (Self::TooSmall, &["too small", "at least", "greater than", "小さすぎ", "以上"]),
+        (Self::TooLarge, &["too large", "too big", "less than", "exceed", "大きすぎ", "以下", "超え"]),
+        (Self::UnsupportedFormat, &["format", "extension", "file type", "not allowed", "形式", "拡張子"]),
+    ];
+
+    pub fn classify(raw: &str) -> Self {
+        let lower = raw.to_lowercase();
+        Self::PATTERNS.iter()
+            .find(|(_, patterns)| patterns.iter().any(|p| lower.contains(p)))
+            .map_or(Self::Unknown, |(reason, _)| *reason)
+    }
+
+    /// メッセージカタログのキー
+    const fn key(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Self::EmptyFile => ("rejection.empty-file", Some("rejection.empty-file.hint")),
+            Self::UnsupportedFormat => ("rejection.unsupported-format", Some("rejection.unsupported-format.hint")),
+            Self::TooLarge => ("rejection.too-large", Some("rejection.too-large.hint")),
+            Self::TooSmall => ("rejection.too-small", Some("rejection.too-small.hint")),
+            Self::Unauthorized => ("rejection.unauthorized", Some("rejection.unauthorized.hint")),
+            Self::QuotaExceeded => ("rejection.quota-exceeded", Some("rejection.quota-exceeded.hint")),
+            Self::CsrfInvalid => ("rejection.csrf-invalid", Some("rejection.csrf-invalid.hint")),
+            Self::CsrfUnavailable => ("rejection.csrf-unavailable", Some("rejection.csrf-unavailable.hint")),
+            Self::NotFound => ("rejection.not-found", Some("rejection.not-found.hint")),
+            Self::ServerError => ("rejection.server-error", Some("rejection.server-error.hint")),
+            Self::Unknown => ("rejection.unknown", None),
+        }
+    }
+}
+
+/// サーバーからのメッセージそのものと、そこから推定した理由
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Rejection {
+    pub reason: RejectionReason,
+    pub raw: String,
+}
+
+impl Rejection {
+    pub fn classify(raw: &str) -> Self {
+        Self {
+            reason: RejectionReason::classify(raw),
+            raw: raw.to_string(),
+        }
+    }
+
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (describe, hint) = self.reason.key();
+        write!(f, "{describe} ({raw})", describe = message::get(describe), raw = self.raw)?;
+        if let Some(hint) = hint {
+            write!(f, "\n  {label}: {hint}", label = message::get("rejection.hint"), hint = message::get(hint))?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Deserialize, Error, Debug)]
 #[error("downloadble: {downloadable:?}")]
 pub struct InnerAggregateError {
@@ -42,7 +192,6 @@ pub struct InnerAggregateError {
 #[derive(Deserialize, Error, Debug)]
 #[error("downloadble: {file:?}")]
 pub struct DownloadableError {
-    #[from]
     file: DownloadableContentError,
 }
 
@@ -58,16 +207,102 @@ pub struct DownloadableContentError(Vec<String>);
 #[error("{0:?}")]
 pub struct InnerError(String);
 
-use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
 use thiserror::Error;
+use crate::form::HtmlForm;
+use crate::message::{self, message};
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct FileId(u32);
 
-#[derive(Deserialize)]
+impl Display for FileId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// アイテムのID。0は使われない
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[serde(try_from = "u32")]
 pub struct ItemId(u32);
 
+impl ItemId {
+    pub const fn new(id: u32) -> Option<Self> {
+        if id == 0 { None } else { Some(Self(id)) }
+    }
+
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for ItemId {
+    type Error = ParseItemRefError;
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        Self::new(id).ok_or_else(|| ParseItemRefError::InvalidId(id.to_string()))
+    }
+}
+
+impl Display for ItemId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// コマンドラインで指定されたアイテム。IDのほか、ストアや管理画面のURL、`name:<名前>`を受け付ける。
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ItemRef {
+    Id(ItemId),
+    /// ショップのアイテム一覧から完全一致で探す
+    Name(String),
+}
+
+#[derive(Error, Debug)]
+pub enum ParseItemRefError {
+    #[error("{}", message!("error.item-ref.invalid-id", id = .0))]
+    InvalidId(String),
+    #[error("{}", message!("error.item-ref.unsupported-url", url = .0))]
+    UnsupportedUrl(String),
+}
+
+impl FromStr for ItemRef {
+    type Err = ParseItemRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(name) = s.strip_prefix("name:") {
+            return Ok(Self::Name(name.to_string()))
+        }
+
+        let parse_id = |id: &str| id.parse().ok().and_then(ItemId::new).ok_or_else(|| ParseItemRefError::InvalidId(id.to_string()));
+        if !s.contains('/') {
+            return parse_id(s).map(Self::Id)
+        }
+
+        // https://booth.pm/ja/items/1、https://shop.booth.pm/items/1、manage.booth.pm/items/1/edit
+        let unsupported = || ParseItemRefError::UnsupportedUrl(s.to_string());
+        let without_scheme = s.strip_prefix("https://").or_else(|| s.strip_prefix("http://")).unwrap_or(s);
+        let without_query = without_scheme.split(['?', '#']).next().unwrap_or_default();
+        let (host, path) = without_query.split_once('/').ok_or_else(unsupported)?;
+        if host != "booth.pm" && !host.ends_with(".booth.pm") {
+            return Err(unsupported())
+        }
+
+        let mut segments = path.split('/');
+        segments.find(|x| *x == "items").ok_or_else(unsupported)?;
+        let id = segments.next().filter(|x| !x.is_empty()).ok_or_else(unsupported)?;
+
+        parse_id(id).map(Self::Id)
+    }
+}
+
 #[derive(Deserialize)]
+#[allow(dead_code)]
 pub struct OpaqueFile {
     pub filename: String,
 }
@@ -81,14 +316,1062 @@ pub struct DiskQuota {
 }
 
 impl DiskQuota {
+    /// 超過している場合は0を返す。
     pub const fn left(&self) -> usize {
-        self.quota - self.usage
+        self.quota.saturating_sub(self.usage)
     }
 }
 
+/// アイテムにアップロード済みのファイルの一覧
 #[derive(Deserialize)]
+pub struct DownloadableList {
+    pub files: Vec<UploadedObject>,
+    pub storage: DiskQuota,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
 pub struct UploadedObject {
     // item_id: ItemId,
+    pub id: FileId,
     pub file_size: usize,
     pub name: String,
 }
+
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct ImageId(u32);
+
+impl Display for ImageId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// アイテムのギャラリーの画像。並びはショップでの表示順
+#[derive(Deserialize)]
+pub struct Gallery {
+    pub images: Vec<GalleryImage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct GalleryImage {
+    pub id: ImageId,
+    /// アップロードしたときのファイル名
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+pub struct UploadedImage {
+    pub image: GalleryImage,
+}
+
+/// BOOTHの日付は日本時間で区切られる
+pub const JST: FixedOffset = match FixedOffset::east_opt(9 * 60 * 60) {
+    Some(offset) => offset,
+    None => panic!("JST offset is in range"),
+};
+
+/// 注文に含まれる商品ひとつ分。一つの注文で複数の商品を買うと、同じ`order_id`で複数になる
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct OrderRecord {
+    pub order_id: u64,
+    pub ordered_at: DateTime<FixedOffset>,
+    pub item_id: ItemId,
+    pub item_name: String,
+    pub variation: String,
+    /// 円
+    pub price: u32,
+    pub payment_method: String,
+    pub status: String,
+}
+
+impl OrderRecord {
+    /// 管理画面の注文一覧の1ページを読む。次のページがあれば`true`も返す。注文は新しい順に並んでいる。
+    pub fn parse_page(html: &str) -> Result<(Vec<Self>, bool), ItemFormError> {
+        use select::predicate::{Attr, Class, Name, Predicate};
+
+        fn attr<'a>(node: &select::node::Node<'a>, name: &'static str) -> Result<&'a str, ItemFormError> {
+            node.attr(name).ok_or(ItemFormError::MissingField(name))
+        }
+
+        let document = select::document::Document::from(html);
+        let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+
+        let mut records = vec![];
+        for order in document.find(Class("manage-order").and(Attr("data-id", ()))) {
+            let order_id = attr(&order, "data-id")?;
+            let order_id = order_id.parse().map_err(|_| invalid("data-id", order_id))?;
+            let ordered_at = attr(&order, "data-ordered-at")?;
+            let ordered_at = DateTime::parse_from_rfc3339(ordered_at).map_err(|_| invalid("data-ordered-at", ordered_at))?;
+            let payment_method = attr(&order, "data-payment-method")?;
+            let status = attr(&order, "data-state")?;
+
+            for line in order.find(Class("order-item")) {
+                let item_id = attr(&line, "data-item-id")?;
+                let item_id = item_id.parse().ok().and_then(ItemId::new).ok_or_else(|| invalid("data-item-id", item_id))?;
+                let price = attr(&line, "data-price")?;
+                let price = price.parse().map_err(|_| invalid("data-price", price))?;
+                let text = |class: &str| line.find(Class(class)).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+
+                records.push(Self {
+                    order_id,
+                    ordered_at,
+                    item_id,
+                    item_name: text("item-name"),
+                    variation: text("variation-name"),
+                    price,
+                    payment_method: payment_method.to_string(),
+                    status: status.to_string(),
+                });
+            }
+        }
+
+        let has_next = document.find(Name("a").and(Attr("rel", "next"))).next().is_some();
+
+        Ok((records, has_next))
+    }
+}
+
+/// 購入者のライブラリにあるアイテム。ギフトとして受け取ったものも含む
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct LibraryItem {
+    pub item_id: ItemId,
+    pub name: String,
+    pub shop: String,
+    pub purchased_at: Option<DateTime<FixedOffset>>,
+    pub gift: bool,
+    pub downloadables: Vec<LibraryFile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct LibraryFile {
+    pub id: u64,
+    pub name: String,
+    /// `https://booth.pm/downloadables/{id}`。実際のファイルへリダイレクトされる
+    pub url: String,
+}
+
+impl LibraryItem {
+    /// ライブラリの1ページを読む。次のページがあれば`true`も返す。
+    pub fn parse_page(html: &str, gift: bool) -> Result<(Vec<Self>, bool), ItemFormError> {
+        use select::predicate::{Attr, Class, Name, Predicate};
+
+        let document = select::document::Document::from(html);
+        let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+
+        let mut items = vec![];
+        for row in document.find(Class("library-item").and(Attr("data-item-id", ()))) {
+            let item_id = row.attr("data-item-id").unwrap_or_default();
+            let item_id = item_id.parse().ok().and_then(ItemId::new).ok_or_else(|| invalid("data-item-id", item_id))?;
+            let purchased_at = row.attr("data-purchased-at")
+                .map(|x| DateTime::parse_from_rfc3339(x).map_err(|_| invalid("data-purchased-at", x)))
+                .transpose()?;
+            let text = |class: &str| row.find(Class(class)).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+
+            let mut downloadables = vec![];
+            for file in row.find(Class("downloadable").and(Attr("data-id", ()))) {
+                let id = file.attr("data-id").unwrap_or_default();
+                let id = id.parse().map_err(|_| invalid("data-id", id))?;
+                let url = file.attr("href").ok_or(ItemFormError::MissingField("href"))?;
+                let name = file.find(Class("file-name")).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+                downloadables.push(LibraryFile { id, name, url: url.to_string() });
+            }
+
+            items.push(Self {
+                item_id,
+                name: text("item-name"),
+                shop: text("shop-name"),
+                purchased_at,
+                gift,
+                downloadables,
+            });
+        }
+
+        let has_next = document.find(Name("a").and(Attr("rel", "next"))).next().is_some();
+
+        Ok((items, has_next))
+    }
+}
+
+/// ほしいものリストにあるアイテム
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct WishlistItem {
+    pub item_id: ItemId,
+    pub name: String,
+    pub shop: String,
+    /// 円。一覧に表示されていなければ`None`
+    pub price: Option<u32>,
+}
+
+impl WishlistItem {
+    /// ほしいものリストの1ページを読む。次のページがあれば`true`も返す。
+    pub fn parse_page(html: &str) -> Result<(Vec<Self>, bool), ItemFormError> {
+        use select::predicate::{Attr, Class, Name, Predicate};
+
+        let document = select::document::Document::from(html);
+        let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+
+        let mut items = vec![];
+        for row in document.find(Class("wish-item").and(Attr("data-item-id", ()))) {
+            let item_id = row.attr("data-item-id").unwrap_or_default();
+            let item_id = item_id.parse().ok().and_then(ItemId::new).ok_or_else(|| invalid("data-item-id", item_id))?;
+            let text = |class: &str| row.find(Class(class)).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+            // ¥1,500
+            let price = text("price").chars().filter(char::is_ascii_digit).collect::<String>().parse().ok();
+
+            items.push(Self { item_id, name: text("item-name"), shop: text("shop-name"), price });
+        }
+
+        let has_next = document.find(Name("a").and(Attr("rel", "next"))).next().is_some();
+
+        Ok((items, has_next))
+    }
+}
+
+/// 管理画面のメッセージの一覧にある、購入者とのやりとりひとつ
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct MessageThread {
+    pub id: u64,
+    pub subject: String,
+    pub buyer: String,
+    pub unread: bool,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl MessageThread {
+    /// メッセージの一覧の1ページを読む。次のページがあれば`true`も返す。新しい順に並んでいる。
+    pub fn parse_page(html: &str) -> Result<(Vec<Self>, bool), ItemFormError> {
+        use select::predicate::{Attr, Class, Name, Predicate};
+
+        let document = select::document::Document::from(html);
+        let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+
+        let mut threads = vec![];
+        for row in document.find(Class("message-thread").and(Attr("data-id", ()))) {
+            let id = row.attr("data-id").unwrap_or_default();
+            let id = id.parse().map_err(|_| invalid("data-id", id))?;
+            let updated_at = row.attr("data-updated-at").ok_or(ItemFormError::MissingField("data-updated-at"))?;
+            let updated_at = DateTime::parse_from_rfc3339(updated_at).map_err(|_| invalid("data-updated-at", updated_at))?;
+            let text = |class: &str| row.find(Class(class)).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+
+            threads.push(Self {
+                id,
+                subject: text("subject"),
+                buyer: text("buyer-name"),
+                unread: row.attr("data-unread") == Some("true"),
+                updated_at,
+            });
+        }
+
+        let has_next = document.find(Name("a").and(Attr("rel", "next"))).next().is_some();
+
+        Ok((threads, has_next))
+    }
+}
+
+/// やりとりの中身。メッセージは古い順
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct MessageThreadDetail {
+    pub id: u64,
+    pub subject: String,
+    pub messages: Vec<ThreadMessage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ThreadMessage {
+    /// ショップから送ったものなら`true`、購入者からなら`false`
+    pub from_shop: bool,
+    pub author: String,
+    pub sent_at: DateTime<FixedOffset>,
+    pub body: String,
+}
+
+impl MessageThreadDetail {
+    pub fn parse(id: u64, html: &str) -> Result<Self, ItemFormError> {
+        use select::predicate::{Attr, Class, Predicate};
+
+        let document = select::document::Document::from(html);
+        let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+
+        let mut messages = vec![];
+        for message in document.find(Class("thread-message").and(Attr("data-sent-at", ()))) {
+            let sent_at = message.attr("data-sent-at").unwrap_or_default();
+            let sent_at = DateTime::parse_from_rfc3339(sent_at).map_err(|_| invalid("data-sent-at", sent_at))?;
+            let text = |class: &str| message.find(Class(class)).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+
+            messages.push(ThreadMessage {
+                from_shop: message.attr("data-sender") == Some("shop"),
+                author: text("author"),
+                sent_at,
+                body: message.find(Class("body")).next().map(|x| multiline_text(&x)).unwrap_or_default(),
+            });
+        }
+
+        let subject = document.find(Class("thread-subject")).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+
+        Ok(Self { id, subject, messages })
+    }
+}
+
+/// ブラウザで見えるとおりの改行を残した本文。`<br>`と段落を改行にし、HTMLの中の改行や字下げは空白一つにする
+fn multiline_text(node: &select::node::Node<'_>) -> String {
+    fn walk(node: &select::node::Node<'_>, out: &mut String) {
+        for child in node.children() {
+            match child.name() {
+                Some("br") => out.push('\n'),
+                Some(name @ ("p" | "div" | "li")) => {
+                    out.push('\n');
+                    walk(&child, out);
+                    out.push_str(if name == "p" { "\n\n" } else { "\n" });
+                }
+                Some(_) => walk(&child, out),
+                None => {
+                    for c in child.as_text().unwrap_or_default().chars() {
+                        if !c.is_whitespace() {
+                            out.push(c);
+                        } else if !out.ends_with([' ', '\n']) {
+                            out.push(' ');
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    walk(node, &mut out);
+
+    // 行ごとの前後の空白を除き、続く空行は一つにまとめる
+    let mut text = String::new();
+    let mut blank = false;
+    for line in out.lines().map(str::trim) {
+        if line.is_empty() {
+            blank = true;
+            continue
+        }
+        if !text.is_empty() {
+            text.push_str(if blank { "\n\n" } else { "\n" });
+        }
+        text.push_str(line);
+        blank = false;
+    }
+
+    text
+}
+
+/// `https://booth.pm/{lang}/items/{id}.json`で公開されている、購入者から見えるアイテムの情報
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PublicItem {
+    pub id: ItemId,
+    pub name: String,
+    pub description: String,
+    /// `¥ 500`や`¥ 500~`のような表示用の文字列
+    pub price: String,
+    pub url: String,
+    pub category: Option<PublicCategory>,
+    pub variations: Vec<PublicVariation>,
+    pub shop: PublicShop,
+    pub images: Vec<PublicImage>,
+    pub tags: Vec<PublicTag>,
+    #[serde(rename = "wish_lists_count")]
+    pub wish_count: u32,
+    pub published_at: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PublicCategory {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PublicVariation {
+    pub id: u64,
+    pub name: Option<String>,
+    pub price: u32,
+    /// 在庫を持たないバリエーションでは`None`
+    pub stock: Option<u32>,
+    /// `on_sale`、`soldout`など
+    pub status: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PublicShop {
+    pub name: String,
+    pub subdomain: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PublicImage {
+    pub original: String,
+    pub resized: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PublicTag {
+    pub name: String,
+}
+
+/// ショップのアイテム一覧の一行
+#[derive(Serialize, Clone, Eq, PartialEq, Debug)]
+pub struct ItemSummary {
+    pub id: ItemId,
+    pub name: String,
+    pub state: PublishState,
+    pub min_price: Option<u32>,
+    pub max_price: Option<u32>,
+    pub variation_count: usize,
+    pub downloadable_count: usize,
+}
+
+impl ItemSummary {
+    /// 管理画面のアイテム一覧の1ページを読む。次のページがあれば`true`も返す。
+    pub fn parse_page(html: &str) -> Result<(Vec<Self>, bool), ItemFormError> {
+        use select::predicate::{Attr, Class, Name, Predicate};
+
+        let document = select::document::Document::from(html);
+        let mut items = vec![];
+        for row in document.find(Class("manage-item").and(Attr("data-id", ()))) {
+            let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+            let id = row.attr("data-id").unwrap_or_default();
+            let id = id.parse().ok().and_then(ItemId::new).ok_or_else(|| invalid("data-id", id))?;
+            let state = row.attr("data-state").unwrap_or_default();
+            let state = state.parse().map_err(|_| invalid("data-state", state))?;
+            let name = row.find(Class("item-name")).next().map(|x| x.text().trim().to_string()).unwrap_or_default();
+            let prices = row.find(Class("variation-price"))
+                .map(|x| {
+                    // ¥1,500
+                    let digits = x.text().chars().filter(char::is_ascii_digit).collect::<String>();
+                    digits.parse::<u32>().map_err(|_| invalid("variation-price", &x.text()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            items.push(Self {
+                id,
+                name,
+                state,
+                min_price: prices.iter().min().copied(),
+                max_price: prices.iter().max().copied(),
+                variation_count: prices.len(),
+                downloadable_count: row.find(Class("downloadable")).count(),
+            });
+        }
+
+        let has_next = document.find(Name("a").and(Attr("rel", "next"))).next().is_some();
+
+        Ok((items, has_next))
+    }
+}
+
+/// `item export`で書き出すアイテムの全体。画像とダウンロードファイルは中身を含まない
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ItemExport {
+    pub item: ItemDraft,
+    /// 表示順
+    pub images: Vec<GalleryImage>,
+    pub downloadables: Vec<UploadedObject>,
+}
+
+/// 編集ページのフォームから読み書きできるアイテムの情報
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ItemDraft {
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub category_id: Option<String>,
+    pub variations: Vec<VariationDraft>,
+    pub age_restriction: AgeRestriction,
+    pub state: PublishState,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct VariationDraft {
+    /// フォームの`item[variations_attributes][n]`の`n`
+    #[serde(skip)]
+    pub index: String,
+    pub id: Option<u64>,
+    pub name: String,
+    /// 円
+    pub price: u32,
+    /// `digital`、`shipping`など。フォームに出ていなければ`None`
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    /// このバリエーションを買った人がダウンロードできるファイル
+    #[serde(default)]
+    pub downloadable_ids: Vec<FileId>,
+    /// 在庫を持つバリエーションだけ。ダウンロード商品では`None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock: Option<u32>,
+}
+
+impl VariationDraft {
+    /// ファイルのIDを`files`の(前のID, 今のID)の対応で置き換える。対応のないものは外す。
+    pub fn remap_downloadables(&mut self, files: &[(FileId, FileId)]) {
+        self.downloadable_ids = self.downloadable_ids.iter()
+            .filter_map(|old| files.iter().find(|(from, _)| from == old).map(|(_, new)| *new))
+            .collect();
+    }
+}
+
+#[derive(Serialize, Deserialize, EnumString, strum::Display, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum AgeRestriction {
+    All,
+    R18,
+}
+
+#[derive(Serialize, Deserialize, EnumString, strum::Display, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum PublishState {
+    Draft,
+    Private,
+    Public,
+}
+
+#[derive(Error, Debug)]
+pub enum ItemFormError {
+    #[error("{}", message!("error.item-form.not-found"))]
+    FormNotFound,
+    #[error("{}", message!("error.item-form.missing-field", field = .0))]
+    MissingField(&'static str),
+    #[error("{}", message!("error.item-form.invalid-value", field = .field, value = .value))]
+    InvalidValue {
+        field: String,
+        value: String,
+    },
+    #[error("{}", message!("error.item-form.unknown-variation", variation = .0))]
+    UnknownVariation(String),
+    #[error("{}", message!("error.item-form.unexpected-redirect", url = .0))]
+    UnexpectedRedirect(String),
+}
+
+impl ItemDraft {
+    const NAME: &'static str = "item[name]";
+    const DESCRIPTION: &'static str = "item[description]";
+    const TAGS: &'static str = "item[tags][]";
+    const CATEGORY: &'static str = "item[category_id]";
+    const ADULT: &'static str = "item[adult]";
+    const STATE: &'static str = "item[state]";
+    const VARIATIONS: &'static str = "item[variations_attributes]";
+    /// 公開予約。予約できるアイテムの編集フォームにだけ出てくる
+    const RESERVATION: &'static str = "item[reserved_published_at]";
+
+    pub fn from_form(form: &HtmlForm) -> Result<Self, ItemFormError> {
+        let required = |field: &'static str| form.get(field).ok_or(ItemFormError::MissingField(field));
+        let invalid = |field: &str, value: &str| ItemFormError::InvalidValue { field: field.to_string(), value: value.to_string() };
+
+        let state = required(Self::STATE)?;
+        let state = state.parse().map_err(|_| invalid(Self::STATE, state))?;
+        // チェックボックスの前に置かれたhiddenの"0"が先に来るので、最後の値を見る
+        let adult = form.get_all(Self::ADULT).last().unwrap_or("0");
+        let age_restriction = if matches!(adult, "1" | "true" | "on") { AgeRestriction::R18 } else { AgeRestriction::All };
+
+        Ok(Self {
+            name: required(Self::NAME)?.to_string(),
+            description: required(Self::DESCRIPTION)?.to_string(),
+            tags: form.get_all(Self::TAGS).filter(|x| !x.is_empty()).map(ToString::to_string).collect(),
+            category_id: form.get(Self::CATEGORY).filter(|x| !x.is_empty()).map(ToString::to_string),
+            variations: Self::variations_from_form(form)?,
+            age_restriction,
+            state,
+        })
+    }
+
+    fn variations_from_form(form: &HtmlForm) -> Result<Vec<VariationDraft>, ItemFormError> {
+        let mut variations: Vec<VariationDraft> = vec![];
+
+        for (name, value) in &form.fields {
+            // item[variations_attributes][0][price]、item[variations_attributes][0][downloadable_ids][]
+            let (single, multiple) = name.strip_suffix("[]").map_or((name.as_str(), false), |x| (x, true));
+            let Some((index, field)) = single.strip_prefix(Self::VARIATIONS)
+                .and_then(|rest| rest.strip_prefix('['))
+                .and_then(|rest| rest.split_once("]["))
+                .and_then(|(index, field)| field.strip_suffix(']').map(|field| (index, field))) else {
+                continue
+            };
+
+            let position = variations.iter().position(|v| v.index == index).unwrap_or_else(|| {
+                variations.push(VariationDraft {
+                    index: index.to_string(),
+                    id: None,
+                    name: String::new(),
+                    price: 0,
+                    kind: None,
+                    downloadable_ids: vec![],
+                    stock: None,
+                });
+                variations.len() - 1
+            });
+            let variation = &mut variations[position];
+            let invalid = || ItemFormError::InvalidValue { field: name.clone(), value: value.clone() };
+
+            match (field, multiple) {
+                ("id", false) if !value.is_empty() => variation.id = Some(value.parse().map_err(|_| invalid())?),
+                ("name", false) => variation.name.clone_from(value),
+                ("price", false) if !value.is_empty() => variation.price = value.parse().map_err(|_| invalid())?,
+                ("type", false) if !value.is_empty() => variation.kind = Some(value.clone()),
+                ("stock", false) if !value.is_empty() => variation.stock = Some(value.parse().map_err(|_| invalid())?),
+                // 全部外したときのために空の値が先頭に置かれている
+                ("downloadable_ids", true) if !value.is_empty() => variation.downloadable_ids.push(FileId(value.parse().map_err(|_| invalid())?)),
+                _ => {}
+            }
+        }
+
+        Ok(variations)
+    }
+
+    fn variation_field(index: &str, field: &str) -> String {
+        format!("{prefix}[{index}][{field}]", prefix = Self::VARIATIONS)
+    }
+
+    /// フォームのうち、このアイテムが表す部分だけを書き換える。他のフィールドはそのまま送り返す。
+    pub fn apply_to(&self, form: &mut HtmlForm) {
+        form.set(Self::NAME, &self.name);
+        form.set(Self::DESCRIPTION, &self.description);
+        // 空の値がないとフィールドごと送られず、全部外しても元のタグが残る
+        form.set_all(Self::TAGS, std::iter::once(String::new()).chain(self.tags.iter().cloned()));
+        if let Some(category_id) = &self.category_id {
+            form.set(Self::CATEGORY, category_id);
+        }
+        form.set_checkbox(Self::ADULT, self.age_restriction == AgeRestriction::R18, "1", "0");
+        form.set(Self::STATE, self.state.to_string());
+
+        // `index`が空のバリエーションはフォームになかったもので、空いている番号で追加する
+        let mut next_index = self.variations.iter().filter_map(|v| v.index.parse::<u32>().ok()).max().map_or(0, |x| x + 1);
+        for variation in &self.variations {
+            let index = if variation.index.is_empty() {
+                next_index += 1;
+                let index = (next_index - 1).to_string();
+                if let Some(kind) = &variation.kind {
+                    form.set(&Self::variation_field(&index, "type"), kind);
+                }
+                index
+            } else {
+                variation.index.clone()
+            };
+
+            form.set(&Self::variation_field(&index, "name"), &variation.name);
+            form.set(&Self::variation_field(&index, "price"), variation.price.to_string());
+            // 物販のバリエーションには欄がないので、紐付けるファイルがなければ足さない
+            let ids_field = format!("{field}[]", field = Self::variation_field(&index, "downloadable_ids"));
+            if !variation.downloadable_ids.is_empty() || form.get(&ids_field).is_some() {
+                let ids = std::iter::once(String::new()).chain(variation.downloadable_ids.iter().map(ToString::to_string));
+                form.set_all(&ids_field, ids);
+            }
+            if let Some(stock) = variation.stock {
+                form.set(&Self::variation_field(&index, "stock"), stock.to_string());
+            }
+        }
+    }
+
+    /// テンプレートのバリエーションで置き換える。既にあるバリエーションは前から順に使い回し、足りなければ追加する。
+    /// フォームに余ったバリエーションは残る。
+    pub fn replace_variations(&mut self, template: &[VariationDraft]) {
+        let existing = std::mem::take(&mut self.variations);
+        let mut existing = existing.into_iter();
+        self.variations = template.iter().map(|source| {
+            let (index, id, kind) = existing.next().map_or_else(
+                || (String::new(), None, source.kind.clone()),
+                |v| (v.index, v.id, v.kind),
+            );
+            VariationDraft { index, id, kind, ..source.clone() }
+        }).collect();
+    }
+
+    /// 公開予約の日時を書き込む。フォームに欄がなければ`false`を返して何もしない。
+    pub fn reserve_publication(form: &mut HtmlForm, at: Option<DateTime<FixedOffset>>) -> bool {
+        if form.get(Self::RESERVATION).is_none() {
+            return false
+        }
+
+        // 編集ページと同じく日本時間で書く
+        let value = at.map(|at| at.with_timezone(&JST).format("%Y-%m-%dT%H:%M").to_string()).unwrap_or_default();
+        form.set(Self::RESERVATION, value);
+        true
+    }
+
+    /// `exported`の内容を書き戻す。バリエーションはIDか名前で対応させ、対応するものがなかった名前を返す。
+    /// ファイルのIDは`files`の(書き出した時点, 今)の対応で置き換え、対応のないものは紐付けない。
+    pub fn restore(&mut self, exported: &Self, files: &[(FileId, FileId)]) -> Vec<String> {
+        self.name.clone_from(&exported.name);
+        self.description.clone_from(&exported.description);
+        self.tags.clone_from(&exported.tags);
+        if exported.category_id.is_some() {
+            self.category_id.clone_from(&exported.category_id);
+        }
+        self.age_restriction = exported.age_restriction;
+        self.state = exported.state;
+
+        let mut unmatched = vec![];
+        for source in &exported.variations {
+            let position = self.variations.iter().position(|v| source.id.is_some() && v.id == source.id)
+                .or_else(|| self.variations.iter().position(|v| v.name == source.name));
+            let Some(target) = position.map(|i| &mut self.variations[i]) else {
+                unmatched.push(source.name.clone());
+                continue
+            };
+
+            target.name.clone_from(&source.name);
+            target.price = source.price;
+            target.downloadable_ids.clone_from(&source.downloadable_ids);
+            target.remap_downloadables(files);
+            // 在庫は書き出した後の注文で減っているので戻さない
+        }
+
+        unmatched
+    }
+
+    /// IDか名前でバリエーションを探す。
+    pub fn variation_mut(&mut self, id_or_name: &str) -> Result<&mut VariationDraft, ItemFormError> {
+        let by_id = id_or_name.parse::<u64>().ok();
+        self.variations.iter_mut()
+            .find(|v| by_id.is_some_and(|id| v.id == Some(id)) || v.name == id_or_name)
+            .ok_or_else(|| ItemFormError::UnknownVariation(id_or_name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use crate::booth::{AgeRestriction, ItemDraft, ItemId, ItemRef, ItemSummary, LibraryItem, MessageThread, MessageThreadDetail, OrderRecord, PublicItem, PublishState, RejectionReason, UploadError, UploadResult, VariationDraft, WishlistItem};
+    use crate::fixture;
+    use crate::form::HtmlForm;
+
+    #[test]
+    fn classify_japanese() {
+        assert_eq!(RejectionReason::classify("ファイルが空です"), RejectionReason::EmptyFile);
+        assert_eq!(RejectionReason::classify("ファイル形式が不正です"), RejectionReason::UnsupportedFormat);
+        assert_eq!(RejectionReason::classify("ファイルサイズは1GB以下にしてください"), RejectionReason::TooLarge);
+        assert_eq!(RejectionReason::classify("ファイルサイズが小さすぎます"), RejectionReason::TooSmall);
+        assert_eq!(RejectionReason::classify("ログインもしくはアカウント登録してください。"), RejectionReason::Unauthorized);
+        assert_eq!(RejectionReason::classify("ショップの容量を超えています"), RejectionReason::QuotaExceeded);
+    }
+
+    #[test]
+    fn classify_english() {
+        assert_eq!(RejectionReason::classify("File is empty"), RejectionReason::EmptyFile);
+        assert_eq!(RejectionReason::classify("File format is invalid"), RejectionReason::UnsupportedFormat);
+        assert_eq!(RejectionReason::classify("File size is too large"), RejectionReason::TooLarge);
+        assert_eq!(RejectionReason::classify("File size is too small"), RejectionReason::TooSmall);
+        assert_eq!(RejectionReason::classify("You need to sign in or sign up before continuing."), RejectionReason::Unauthorized);
+        assert_eq!(RejectionReason::classify("Disk quota exceeded"), RejectionReason::QuotaExceeded);
+        assert_eq!(RejectionReason::classify("Can't verify CSRF token authenticity."), RejectionReason::CsrfInvalid);
+        assert_eq!(RejectionReason::classify("something went wrong"), RejectionReason::Unknown);
+    }
+
+    #[test]
+    fn deserialize_rejections() {
+        let UploadResult::Err(error) = serde_json::from_str(r#"{"errors":{"downloadable":{"file":["File is empty","File format is invalid"]}}}"#).unwrap() else {
+            panic!("must be error")
+        };
+        let reasons = error.rejections().iter().map(|r| r.reason).collect::<Vec<_>>();
+        assert_eq!(reasons, [RejectionReason::EmptyFile, RejectionReason::UnsupportedFormat]);
+
+        let UploadResult::Err(error) = serde_json::from_str(r#"{"error":"You need to sign in or sign up before continuing."}"#).unwrap() else {
+            panic!("must be error")
+        };
+        assert_eq!(error.rejections()[0].reason, RejectionReason::Unauthorized);
+        assert_eq!(error.rejections()[0].raw, "You need to sign in or sign up before continuing.");
+
+        let error = UploadError::Status { status: 422, body: String::new() };
+        assert_eq!(error.rejections()[0].reason, RejectionReason::CsrfInvalid);
+        assert_eq!(error.rejections()[0].raw, "HTTP 422");
+
+        // どのページにもナビゲーションの「ログイン」がある
+        let page = "<html><head><title>Bad Gateway | BOOTH</title></head><body><a href=\"/users/sign_in\">ログイン</a></body></html>";
+        let error = UploadError::Status { status: 502, body: page.to_string() };
+        assert_eq!(error.rejections()[0].reason, RejectionReason::ServerError);
+        assert_eq!(error.rejections()[0].raw, "HTTP 502: Bad Gateway | BOOTH");
+        let error = UploadError::Status { status: 404, body: page.to_string() };
+        assert_eq!(error.rejections()[0].reason, RejectionReason::NotFound);
+        let error = UploadError::Status { status: 400, body: page.to_string() };
+        assert_eq!(error.rejections()[0].reason, RejectionReason::Unknown);
+        let error = UploadError::Status { status: 400, body: r#"{"error":"Disk quota exceeded"}"#.to_string() };
+        assert_eq!(error.rejections()[0].reason, RejectionReason::QuotaExceeded);
+        assert_eq!(error.rejections()[0].raw, r#"HTTP 400: {"error":"Disk quota exceeded"}"#);
+    }
+
+    const EDIT_PAGE: &str = r#"<form action="https://manage.booth.pm/items/123" method="post">
+        <input type="hidden" name="_method" value="patch">
+        <input type="text" name="item[name]" value="Sample">
+        <textarea name="item[description]">v1.0.0</textarea>
+        <input type="hidden" name="item[tags][]" value="VRChat">
+        <input type="hidden" name="item[tags][]" value="3D">
+        <select name="item[category_id]"><option value="208" selected>3D</option></select>
+        <input type="hidden" name="item[adult]" value="0">
+        <input type="checkbox" name="item[adult]" value="1">
+        <select name="item[state]"><option value="draft">draft</option><option value="public" selected>public</option></select>
+        <input type="hidden" name="item[variations_attributes][0][id]" value="11">
+        <input type="text" name="item[variations_attributes][0][name]" value="Basic">
+        <input type="number" name="item[variations_attributes][0][price]" value="500">
+        <input type="hidden" name="item[variations_attributes][0][type]" value="digital">
+        <input type="hidden" name="item[variations_attributes][0][downloadable_ids][]" value="">
+        <input type="checkbox" name="item[variations_attributes][0][downloadable_ids][]" value="101" checked>
+        <input type="checkbox" name="item[variations_attributes][0][downloadable_ids][]" value="102">
+        <input type="hidden" name="item[variations_attributes][1][id]" value="12">
+        <input type="text" name="item[variations_attributes][1][name]" value="Pro">
+        <input type="number" name="item[variations_attributes][1][price]" value="1500">
+    </form>"#;
+
+    #[test]
+    fn item_draft_round_trip() {
+        let mut form = HtmlForm::find(EDIT_PAGE, "/items/123").unwrap();
+        let mut draft = ItemDraft::from_form(&form).unwrap();
+        assert_eq!(draft.name, "Sample");
+        assert_eq!(draft.tags, ["VRChat", "3D"]);
+        assert_eq!(draft.category_id.as_deref(), Some("208"));
+        assert_eq!(draft.age_restriction, AgeRestriction::All);
+        assert_eq!(draft.state, PublishState::Public);
+        assert_eq!(draft.variations.iter().map(|v| (v.id, v.name.as_str(), v.price)).collect::<Vec<_>>(), [(Some(11), "Basic", 500), (Some(12), "Pro", 1500)]);
+        assert_eq!(draft.variations[0].kind.as_deref(), Some("digital"));
+        assert_eq!(draft.variations[0].downloadable_ids.iter().map(ToString::to_string).collect::<Vec<_>>(), ["101"]);
+        assert!(draft.variations[1].downloadable_ids.is_empty());
+
+        draft.description = "v1.1.0".to_string();
+        draft.tags.push("avatar".to_string());
+        draft.age_restriction = AgeRestriction::R18;
+        draft.variation_mut("Pro").unwrap().price = 2000;
+        let moved = std::mem::take(&mut draft.variations[0].downloadable_ids);
+        draft.variation_mut("Pro").unwrap().downloadable_ids.extend(moved);
+        assert!(draft.variation_mut("13").is_err());
+        draft.apply_to(&mut form);
+
+        assert_eq!(form.get("_method"), Some("patch"));
+        assert_eq!(ItemDraft::from_form(&form).unwrap(), draft);
+
+        draft.tags.clear();
+        draft.apply_to(&mut form);
+        assert_eq!(form.get_all("item[tags][]").collect::<Vec<_>>(), [""]);
+    }
+
+    #[test]
+    fn variation_stock() {
+        let mut form = HtmlForm::find(EDIT_PAGE, "/items/123").unwrap();
+        form.set("item[variations_attributes][1][stock]", "3");
+        let mut draft = ItemDraft::from_form(&form).unwrap();
+        assert_eq!(draft.variations.iter().map(|v| v.stock).collect::<Vec<_>>(), [None, Some(3)]);
+
+        draft.variation_mut("Pro").unwrap().stock = Some(10);
+        draft.apply_to(&mut form);
+        assert_eq!(form.get("item[variations_attributes][1][stock]"), Some("10"));
+        assert_eq!(form.get("item[variations_attributes][0][stock]"), None);
+        assert_eq!(form.get("item[variations_attributes][1][downloadable_ids][]"), None);
+    }
+
+    #[test]
+    fn reserve_publication() {
+        let mut form = HtmlForm::find(EDIT_PAGE, "/items/123").unwrap();
+        let at = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap();
+        assert!(!ItemDraft::reserve_publication(&mut form, Some(at)));
+        assert_eq!(form.get("item[reserved_published_at]"), None);
+
+        form.set("item[reserved_published_at]", "");
+        assert!(ItemDraft::reserve_publication(&mut form, Some(at)));
+        assert_eq!(form.get("item[reserved_published_at]"), Some("2026-01-01T09:00"));
+        assert!(ItemDraft::reserve_publication(&mut form, None));
+        assert_eq!(form.get("item[reserved_published_at]"), Some(""));
+    }
+
+    #[test]
+    fn add_variations() {
+        let mut form = HtmlForm::find(EDIT_PAGE, "/items/123").unwrap();
+        let mut draft = ItemDraft::from_form(&form).unwrap();
+        let mut template = draft.variations.clone();
+        template.push(VariationDraft { index: String::new(), kind: Some("digital".to_string()), ..fixture::variation(0, 99, "Ultimate", 5000) });
+        template.reverse();
+
+        draft.replace_variations(&template);
+        assert_eq!(draft.variations.iter().map(|v| (v.index.as_str(), v.id, v.name.as_str())).collect::<Vec<_>>(), [("0", Some(11), "Ultimate"), ("1", Some(12), "Pro"), ("", None, "Basic")]);
+
+        draft.apply_to(&mut form);
+        assert_eq!(form.get("item[variations_attributes][2][name]"), Some("Basic"));
+        assert_eq!(form.get("item[variations_attributes][2][type]"), Some("digital"));
+        let reparsed = ItemDraft::from_form(&form).unwrap();
+        assert_eq!(reparsed.variations.iter().map(|v| (v.name.as_str(), v.price)).collect::<Vec<_>>(), [("Ultimate", 5000), ("Pro", 1500), ("Basic", 500)]);
+    }
+
+    #[test]
+    fn item_list_page() {
+        let page = r#"<html><body>
+            <div class="manage-item" data-id="3519955" data-state="public">
+                <a class="item-name" href="/items/3519955/edit"> Sample </a>
+                <div class="variation"><span class="variation-price">¥500</span><span class="downloadable">basic.zip</span></div>
+                <div class="variation"><span class="variation-price">¥1,500</span><span class="downloadable">pro.zip</span><span class="downloadable">extra.zip</span></div>
+            </div>
+            <div class="manage-item" data-id="42" data-state="draft"><a class="item-name">New</a></div>
+            <a rel="next" href="/items?page=2">next</a>
+        </body></html>"#;
+
+        let (items, has_next) = ItemSummary::parse_page(page).unwrap();
+        assert!(has_next);
+        assert_eq!(items[0], ItemSummary {
+            id: ItemId::new(3_519_955).unwrap(),
+            name: "Sample".to_string(),
+            state: PublishState::Public,
+            min_price: Some(500),
+            max_price: Some(1500),
+            variation_count: 2,
+            downloadable_count: 3,
+        });
+        assert_eq!((items[1].id, items[1].state, items[1].min_price), (ItemId::new(42).unwrap(), PublishState::Draft, None));
+        assert!(!ItemSummary::parse_page("<html></html>").unwrap().1);
+    }
+
+    #[test]
+    fn item_ref() {
+        let id = |x: u32| Ok(ItemRef::Id(ItemId::new(x).unwrap()));
+        let parse = |x: &str| x.parse::<ItemRef>().map_err(|e| e.to_string());
+        assert_eq!(parse("3519955"), id(3_519_955));
+        assert_eq!(parse("https://booth.pm/ja/items/3519955"), id(3_519_955));
+        assert_eq!(parse("https://booth.pm/ja/items/3519955?foo=bar#top"), id(3_519_955));
+        assert_eq!(parse("https://shopname.booth.pm/items/3519955"), id(3_519_955));
+        assert_eq!(parse("manage.booth.pm/items/3519955/edit"), id(3_519_955));
+        assert_eq!(parse("name:Sample"), Ok(ItemRef::Name("Sample".to_string())));
+        assert!(parse("-1").is_err());
+        assert!(parse("0").is_err());
+        assert!(parse("https://example.com/items/1").is_err());
+        assert!(parse("https://booth.pm/ja/").is_err());
+    }
+
+    #[test]
+    fn deserialize_item_id() {
+        assert_eq!(serde_json::from_str::<ItemId>("42").ok(), ItemId::new(42));
+        assert!(serde_json::from_str::<ItemId>("0").is_err());
+    }
+
+    #[test]
+    fn deserialize_public_item() {
+        let json = r#"{
+            "id": 3519955,
+            "name": "Sample",
+            "description": "v1.0.0",
+            "price": "¥ 500~",
+            "url": "https://shopname.booth.pm/items/3519955",
+            "is_adult": false,
+            "category": { "id": 208, "name": "3Dモデル" },
+            "variations": [
+                { "id": 11, "name": "Basic", "price": 500, "stock": null, "status": "on_sale", "type": "digital", "has_download_source": true },
+                { "id": 12, "name": null, "price": 1500, "stock": 3, "status": "soldout", "type": "direct" }
+            ],
+            "shop": { "name": "Shop", "subdomain": "shopname", "url": "https://shopname.booth.pm/", "verified": true },
+            "images": [{ "original": "https://example.com/1.png", "resized": null }],
+            "tags": [{ "name": "VRChat", "url": "https://booth.pm/ja/browse?tags%5B%5D=VRChat" }],
+            "wish_lists_count": 42,
+            "published_at": "2022-01-01T12:00:00.000+09:00"
+        }"#;
+
+        let item = serde_json::from_str::<PublicItem>(json).unwrap();
+        assert_eq!(item.id, ItemId::new(3_519_955).unwrap());
+        assert_eq!(item.variations[1].stock, Some(3));
+        assert_eq!(item.variations[1].name, None);
+        assert_eq!(item.shop.subdomain, "shopname");
+        assert_eq!(item.tags[0].name, "VRChat");
+        assert_eq!(item.wish_count, 42);
+        assert_eq!(item.published_at.unwrap().to_rfc3339(), "2022-01-01T12:00:00+09:00");
+    }
+
+    #[test]
+    fn order_list_page() {
+        let page = r#"<html><body>
+            <div class="manage-order" data-id="1002" data-ordered-at="2026-09-30T23:30:00+09:00" data-payment-method="credit_card" data-state="completed">
+                <div class="order-item" data-item-id="3519955" data-price="500"><span class="item-name">Sample</span><span class="variation-name"> Basic </span></div>
+                <div class="order-item" data-item-id="42" data-price="1500"><span class="item-name">Other</span><span class="variation-name">Pro</span></div>
+            </div>
+            <div class="manage-order" data-id="1001" data-ordered-at="2026-09-01T00:10:00+09:00" data-payment-method="paypal" data-state="cancelled">
+                <div class="order-item" data-item-id="3519955" data-price="500"><span class="item-name">Sample</span><span class="variation-name">Basic</span></div>
+            </div>
+        </body></html>"#;
+
+        let (records, has_next) = OrderRecord::parse_page(page).unwrap();
+        assert!(!has_next);
+        assert_eq!(records.iter().map(|x| (x.order_id, x.item_id.to_string(), x.variation.as_str(), x.price)).collect::<Vec<_>>(), [
+            (1002, "3519955".to_string(), "Basic", 500),
+            (1002, "42".to_string(), "Pro", 1500),
+            (1001, "3519955".to_string(), "Basic", 500),
+        ]);
+        assert_eq!(records[2].payment_method, "paypal");
+        assert_eq!(records[2].status, "cancelled");
+    }
+
+    #[test]
+    fn library_page() {
+        let page = r#"<html><body>
+            <div class="library-item" data-item-id="3519955" data-purchased-at="2026-09-01T12:00:00+09:00">
+                <a class="item-name" href="https://booth.pm/ja/items/3519955"> Sample </a><span class="shop-name">Shop</span>
+                <a class="downloadable" data-id="101" href="https://booth.pm/downloadables/101"><span class="file-name">v1.zip</span></a>
+                <a class="downloadable" data-id="102" href="https://booth.pm/downloadables/102"><span class="file-name">manual.pdf</span></a>
+            </div>
+            <div class="library-item" data-item-id="42"><a class="item-name">Physical</a><span class="shop-name">Other</span></div>
+            <a rel="next" href="/library?page=2">next</a>
+        </body></html>"#;
+
+        let (items, has_next) = LibraryItem::parse_page(page, true).unwrap();
+        assert!(has_next);
+        assert_eq!(items.len(), 2);
+        assert_eq!((items[0].item_id.to_string(), items[0].name.as_str(), items[0].shop.as_str(), items[0].gift), ("3519955".to_string(), "Sample", "Shop", true));
+        assert_eq!(items[0].purchased_at.unwrap().to_rfc3339(), "2026-09-01T12:00:00+09:00");
+        assert_eq!(items[0].downloadables.iter().map(|x| (x.id, x.name.as_str())).collect::<Vec<_>>(), [(101, "v1.zip"), (102, "manual.pdf")]);
+        assert_eq!(items[0].downloadables[0].url, "https://booth.pm/downloadables/101");
+        assert!(items[1].purchased_at.is_none());
+        assert!(items[1].downloadables.is_empty());
+    }
+
+    #[test]
+    fn wishlist_page() {
+        let page = r#"<html><body>
+            <div class="wish-item" data-item-id="3519955"><a class="item-name">Sample</a><span class="shop-name">Shop</span><span class="price">¥ 1,500</span></div>
+            <div class="wish-item" data-item-id="42"><a class="item-name">Other</a><span class="shop-name">Shop</span></div>
+        </body></html>"#;
+
+        let (items, has_next) = WishlistItem::parse_page(page).unwrap();
+        assert!(!has_next);
+        assert_eq!(items.iter().map(|x| (x.item_id.to_string(), x.name.as_str(), x.price)).collect::<Vec<_>>(), [
+            ("3519955".to_string(), "Sample", Some(1500)),
+            ("42".to_string(), "Other", None),
+        ]);
+    }
+
+    #[test]
+    fn message_pages() {
+        let page = r#"<html><body>
+            <div class="message-thread" data-id="901" data-unread="true" data-updated-at="2026-10-01T09:00:00+09:00"><span class="subject">Question</span><span class="buyer-name">buyer</span></div>
+            <div class="message-thread" data-id="900" data-unread="false" data-updated-at="2026-09-01T09:00:00+09:00"><span class="subject">Thanks</span><span class="buyer-name">other</span></div>
+            <a rel="next" href="/messages?page=2">next</a>
+        </body></html>"#;
+
+        let (threads, has_next) = MessageThread::parse_page(page).unwrap();
+        assert!(has_next);
+        assert_eq!(threads.iter().map(|x| (x.id, x.subject.as_str(), x.buyer.as_str(), x.unread)).collect::<Vec<_>>(), [
+            (901, "Question", "buyer", true),
+            (900, "Thanks", "other", false),
+        ]);
+
+        let page = r#"<html><body>
+            <h1 class="thread-subject">Question</h1>
+            <div class="thread-message" data-sender="buyer" data-sent-at="2026-10-01T09:00:00+09:00"><span class="author">buyer</span><div class="body">
+                Does v2 support Quest?
+            </div></div>
+            <div class="thread-message" data-sender="shop" data-sent-at="2026-10-01T10:00:00+09:00"><span class="author">Shop</span><div class="body">Yes.</div></div>
+        </body></html>"#;
+
+        let thread = MessageThreadDetail::parse(901, page).unwrap();
+        assert_eq!(thread.subject, "Question");
+        assert_eq!(thread.messages.iter().map(|x| (x.from_shop, x.author.as_str(), x.body.as_str())).collect::<Vec<_>>(), [
+            (false, "buyer", "Does v2 support Quest?"),
+            (true, "Shop", "Yes."),
+        ]);
+
+        let page = r#"<div class="thread-message" data-sender="buyer" data-sent-at="2026-10-01T09:00:00+09:00"><div class="body">
+            <p>Hello,<br>
+            does v2   support <b>Quest</b>?</p>
+            <p>Thanks.</p>
+        </div></div>"#;
+        let thread = MessageThreadDetail::parse(901, page).unwrap();
+        assert_eq!(thread.messages[0].body, "Hello,\ndoes v2 support Quest?\n\nThanks.");
+    }
+}
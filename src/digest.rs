@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use crate::booth::{DiskQuota, ItemDraft, ItemId, MessageThread, OrderRecord, PublicItem, PublishState, JST};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::pretty_size::pretty_size;
+use crate::public::PublicClient;
+use crate::sales::VOID_STATUSES;
+
+const DEFAULT_STATE_FILE_NAME: &str = ".kisaragi-booth-digest.json";
+
+/// 前回の実行の記録
+#[derive(Serialize, Deserialize, Default)]
+struct DigestState {
+    last_run: Option<DateTime<FixedOffset>>,
+    /// アイテムごとのスキの数
+    wishes: BTreeMap<ItemId, u32>,
+}
+
+impl DigestState {
+    fn load(path: &Path) -> Result<Self, ExecutionError> {
+        if path.exists() {
+            let bytes = std::fs::read(path)?;
+            Ok(serde_json::from_slice(&bytes)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), ExecutionError> {
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct Digest {
+    since: DateTime<FixedOffset>,
+    /// キャンセルされた注文は数えない
+    orders: usize,
+    /// キャンセルされた注文を除いた円
+    revenue: u64,
+    updated_threads: usize,
+    /// 更新されたやりとりのうち未読のもの
+    unread_threads: usize,
+    wishes: Vec<WishChange>,
+    low_stock: Vec<LowStock>,
+    quota: Option<QuotaUsage>,
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct WishChange {
+    item_id: ItemId,
+    name: String,
+    from: u32,
+    to: u32,
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct LowStock {
+    item_id: ItemId,
+    name: String,
+    variation: String,
+    stock: u32,
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct QuotaUsage {
+    quota: usize,
+    usage: usize,
+    left: usize,
+}
+
+/// ネットワークから集めたものをまとめる。前回のスキの数がないアイテムは、今回の数を記録するだけ。
+/// 公開ページのないアイテムの在庫は`drafts`の編集ページから読む
+#[allow(clippy::too_many_arguments)]
+fn summarize(
+    since: DateTime<FixedOffset>,
+    orders: &[OrderRecord],
+    threads: &[MessageThread],
+    public_items: &[PublicItem],
+    drafts: &[(ItemId, ItemDraft)],
+    previous_wishes: &BTreeMap<ItemId, u32>,
+    low_stock_threshold: u32,
+    quota: Option<&DiskQuota>,
+) -> Digest {
+    let valid = orders.iter().filter(|x| !VOID_STATUSES.contains(&x.status.as_str())).collect::<Vec<_>>();
+    let mut order_ids = valid.iter().map(|x| x.order_id).collect::<Vec<_>>();
+    order_ids.sort_unstable();
+    order_ids.dedup();
+
+    let wishes = public_items.iter()
+        .filter_map(|item| {
+            let from = *previous_wishes.get(&item.id)?;
+            (from != item.wish_count).then(|| WishChange { item_id: item.id, name: item.name.clone(), from, to: item.wish_count })
+        })
+        .collect();
+
+    // 在庫を持たないバリエーションは`None`
+    let public_stocks = public_items.iter()
+        .flat_map(|item| item.variations.iter().map(move |v| (item.id, &item.name, v.name.clone().unwrap_or_default(), v.stock)));
+    let draft_stocks = drafts.iter()
+        .flat_map(|(id, item)| item.variations.iter().map(move |v| (*id, &item.name, v.name.clone(), v.stock)));
+    let low_stock = public_stocks.chain(draft_stocks)
+        .filter_map(|(item_id, name, variation, stock)| {
+            let stock = stock.filter(|x| *x <= low_stock_threshold)?;
+            Some(LowStock { item_id, name: name.clone(), variation, stock })
+        })
+        .collect();
+
+    Digest {
+        since,
+        orders: order_ids.len(),
+        revenue: valid.iter().map(|x| u64::from(x.price)).sum(),
+        updated_threads: threads.iter().filter(|x| x.updated_at >= since).count(),
+        unread_threads: threads.iter().filter(|x| x.updated_at >= since && x.unread).count(),
+        wishes,
+        low_stock,
+        quota: quota.map(|x| QuotaUsage { quota: x.quota, usage: x.usage, left: x.left() }),
+    }
+}
+
+fn print_digest(digest: &Digest) {
+    println!("{}", message!("digest.since", since = digest.since.with_timezone(&JST).format("%Y-%m-%d %H:%M")));
+    println!("{}", message!("digest.orders", count = digest.orders, revenue = digest.revenue));
+    println!("{}", message!("digest.messages", updated = digest.updated_threads, unread = digest.unread_threads));
+    for wish in &digest.wishes {
+        println!("{}", message!("digest.wishes", id = wish.item_id, name = wish.name, from = wish.from, to = wish.to));
+    }
+    for low in &digest.low_stock {
+        println!("{}", message!("digest.low-stock", id = low.item_id, name = low.name, variation = low.variation, stock = low.stock));
+    }
+    if let Some(quota) = &digest.quota {
+        println!("{}", message!("digest.quota", used = pretty_size(quota.usage), quota = pretty_size(quota.quota), left = pretty_size(quota.left)));
+    }
+}
+
+/// 前回からの注文、メッセージ、スキの数の変化、在庫の少ないバリエーションと容量をまとめて表示する。
+/// 初めての実行では、直前の24時間を対象にする。BOOTHにはレビューがないので、反応はスキの数だけを見る。
+/// スキの数は公開ページにしかないので、非公開や下書きのアイテムでは変化を見られない。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn it(client: &ManageClient, state_file: Option<&Path>, low_stock_threshold: u32, json: bool) -> Result<(), ExecutionError> {
+    let state_file = state_file.unwrap_or_else(|| Path::new(DEFAULT_STATE_FILE_NAME));
+    let mut state = DigestState::load(state_file)?;
+    let now = Utc::now().with_timezone(&JST);
+    let since = state.last_run.unwrap_or(now - Duration::days(1));
+
+    let orders = client.orders_since(since).await?;
+    let threads = client.message_threads_since(since).await?;
+    let items = client.items().await?;
+
+    // スキの数と在庫は公開されているページから読み、公開ページがなければ在庫だけを編集ページから読む
+    let public = PublicClient::new();
+    let mut public_items = vec![];
+    let mut drafts = vec![];
+    for item in &items {
+        let found = if item.state == PublishState::Public { public.item(item.id).await? } else { None };
+        if let Some(found) = found {
+            public_items.push(found);
+        } else {
+            let (form, _) = client.edit_form(item.id).await?;
+            drafts.push((item.id, ItemDraft::from_form(&form)?));
+        }
+    }
+
+    // 容量はアカウントで共通なので、どのアイテムから見てもよい
+    let quota = match items.first() {
+        Some(item) => Some(client.downloadables(item.id).await?.storage),
+        None => None,
+    };
+
+    let digest = summarize(since, &orders, &threads, &public_items, &drafts, &state.wishes, low_stock_threshold, quota.as_ref());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&digest)?);
+    } else {
+        print_digest(&digest);
+    }
+
+    state.last_run = Some(now);
+    state.wishes = public_items.iter().map(|x| (x.id, x.wish_count)).collect();
+    state.save(state_file)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use chrono::DateTime;
+    use crate::booth::{DiskQuota, ItemDraft, ItemId, MessageThread, OrderRecord, PublicItem, PublishState, VariationDraft};
+    use crate::digest::{summarize, LowStock, QuotaUsage, WishChange};
+    use crate::fixture;
+
+    fn public_item(id: u32, wish_count: u32, stocks: &[Option<u32>]) -> PublicItem {
+        let variations = stocks.iter().enumerate()
+            .map(|(i, stock)| serde_json::json!({ "id": i, "name": format!("v{i}"), "price": 500, "stock": stock, "status": "on_sale", "type": "physical" }))
+            .collect::<Vec<_>>();
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": format!("Item {id}"), "description": "", "price": "¥ 500", "url": "", "category": null,
+            "variations": variations, "shop": { "name": "", "subdomain": "", "url": "" },
+            "images": [], "tags": [], "wish_lists_count": wish_count, "published_at": null,
+        })).unwrap()
+    }
+
+    #[test]
+    fn summary() {
+        let at = |x: &str| DateTime::parse_from_rfc3339(x).unwrap();
+        let since = at("2026-10-18T09:00:00+09:00");
+        let order = |order_id: u64, price: u32, status: &str| OrderRecord {
+            order_id,
+            ordered_at: at("2026-10-18T12:00:00+09:00"),
+            item_id: ItemId::new(1).unwrap(),
+            item_name: String::new(),
+            variation: String::new(),
+            price,
+            payment_method: String::new(),
+            status: status.to_string(),
+        };
+        let thread = |id: u64, unread: bool, updated_at: &str| MessageThread { id, subject: String::new(), buyer: String::new(), unread, updated_at: at(updated_at) };
+
+        let orders = [order(1, 500, "completed"), order(1, 1000, "completed"), order(2, 700, "cancelled")];
+        let threads = [thread(1, true, "2026-10-18T10:00:00+09:00"), thread(2, false, "2026-10-17T10:00:00+09:00"), thread(3, true, "2026-10-01T10:00:00+09:00")];
+        let items = [public_item(1, 12, &[None]), public_item(2, 3, &[Some(2), Some(10)]), public_item(3, 1, &[])];
+        let previous = BTreeMap::from([(ItemId::new(1).unwrap(), 10), (ItemId::new(2).unwrap(), 3)]);
+        let quota = DiskQuota { quota: 1000, usage: 400 };
+
+        // 非公開のアイテムの在庫は編集ページから読む
+        let private = ItemDraft {
+            state: PublishState::Private,
+            ..fixture::draft(vec![VariationDraft { stock: Some(1), ..fixture::variation(0, 41, "Goods", 1500) }, fixture::variation(1, 42, "Digital", 500)])
+        };
+        let drafts = [(ItemId::new(4).unwrap(), private)];
+
+        let digest = summarize(since, &orders, &threads, &items, &drafts, &previous, 5, Some(&quota));
+        assert_eq!((digest.orders, digest.revenue), (1, 1500));
+        assert_eq!((digest.updated_threads, digest.unread_threads), (1, 1));
+        assert_eq!(digest.wishes, [WishChange { item_id: ItemId::new(1).unwrap(), name: "Item 1".to_string(), from: 10, to: 12 }]);
+        assert_eq!(digest.low_stock, [
+            LowStock { item_id: ItemId::new(2).unwrap(), name: "Item 2".to_string(), variation: "v0".to_string(), stock: 2 },
+            LowStock { item_id: ItemId::new(4).unwrap(), name: "Sample".to_string(), variation: "Goods".to_string(), stock: 1 },
+        ]);
+        assert_eq!(digest.quota, Some(QuotaUsage { quota: 1000, usage: 400, left: 600 }));
+    }
+}
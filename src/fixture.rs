@@ -0,0 +1,39 @@
+//! テストで使う、管理画面から読んだのと同じ形のデータ
+
+use crate::booth::{AgeRestriction, GalleryImage, ItemDraft, PublishState, UploadedObject, VariationDraft};
+
+/// `index`番目の、ダウンロードファイルも在庫もないバリエーション
+pub fn variation(index: usize, id: u64, name: &str, price: u32) -> VariationDraft {
+    VariationDraft {
+        index: index.to_string(),
+        id: Some(id),
+        name: name.to_string(),
+        price,
+        kind: None,
+        downloadable_ids: vec![],
+        stock: None,
+    }
+}
+
+/// 公開中の`Sample`。ほかの値は`ItemDraft { .., ..draft(..) }`で変える
+pub fn draft(variations: Vec<VariationDraft>) -> ItemDraft {
+    ItemDraft {
+        name: "Sample".to_string(),
+        description: String::new(),
+        tags: vec![],
+        category_id: None,
+        variations,
+        age_restriction: AgeRestriction::All,
+        state: PublishState::Public,
+    }
+}
+
+/// アップロード済みのダウンロードファイル。IDは外から作れないのでJSONを通す
+pub fn uploaded(id: u32, name: &str, file_size: usize) -> UploadedObject {
+    serde_json::from_value(serde_json::json!({ "id": id, "name": name, "file_size": file_size })).unwrap()
+}
+
+/// ギャラリーにある画像
+pub fn image(id: u32, name: &str) -> GalleryImage {
+    serde_json::from_value(serde_json::json!({ "id": id, "name": name, "url": "" })).unwrap()
+}
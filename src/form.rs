@@ -0,0 +1,243 @@
+use reqwest::Url;
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Attr, Class, Name, Predicate};
+use thiserror::Error;
+use crate::message::message;
+
+#[derive(Error, Debug)]
+pub enum SubmitError {
+    #[error("{}", message!("error.submit.foreign-action", action = .0))]
+    ForeignAction(String),
+    #[error("{}", message!("error.submit.rejected", errors = .0))]
+    Rejected(String),
+    #[error("{}", message!("error.submit.rejected-without-message"))]
+    RejectedWithoutMessage,
+    #[error("{}", message!("error.submit.signed-out"))]
+    SignedOut,
+}
+
+/// HTMLの`<form>`をブラウザが送信するのと同じ形で取り出したもの。
+/// 値を書き換えてそのまま送り返せるように、フィールドの順序と重複を保つ。
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HtmlForm {
+    pub action: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl HtmlForm {
+    /// `action`が`action_suffix`で終わる最初のフォーム
+    pub fn find(html: &str, action_suffix: &str) -> Option<Self> {
+        Self::find_by(html, |form| form.attr("action").is_some_and(|action| action.trim_end_matches('/').ends_with(action_suffix)))
+    }
+
+    /// `name`という名前の入力欄を持つ最初のフォーム。`action`の形が決まっていないときに使う
+    pub fn find_with_field(html: &str, name: &str) -> Option<Self> {
+        Self::find_by(html, |form| form.find(Attr("name", name)).next().is_some())
+    }
+
+    fn find_by(html: &str, predicate: impl Fn(&Node<'_>) -> bool) -> Option<Self> {
+        let document = Document::from(html);
+        let form = document.find(Name("form")).find(predicate)?;
+
+        Some(Self {
+            action: form.attr("action").unwrap_or_default().to_string(),
+            fields: Self::collect_fields(&form),
+        })
+    }
+
+    fn collect_fields(form: &Node<'_>) -> Vec<(String, String)> {
+        let mut fields = vec![];
+        let controls = Name("input").or(Name("textarea")).or(Name("select"));
+
+        for control in form.find(controls) {
+            let Some(name) = control.attr("name") else {
+                continue
+            };
+            if control.attr("disabled").is_some() {
+                continue
+            }
+
+            match control.name() {
+                Some("input") => {
+                    let kind = control.attr("type").unwrap_or("text").to_ascii_lowercase();
+                    let value = control.attr("value");
+                    match kind.as_str() {
+                        "submit" | "button" | "image" | "reset" | "file" => {}
+                        "checkbox" | "radio" => {
+                            if control.attr("checked").is_some() {
+                                fields.push((name.to_string(), value.unwrap_or("on").to_string()));
+                            }
+                        }
+                        _ => fields.push((name.to_string(), value.unwrap_or_default().to_string())),
+                    }
+                }
+                Some("textarea") => fields.push((name.to_string(), control.text())),
+                Some("select") => {
+                    let options = control.find(Name("option")).collect::<Vec<_>>();
+                    let option_value = |option: &Node<'_>| option.attr("value").map_or_else(|| option.text(), ToString::to_string);
+                    let selected = options.iter().filter(|o| o.attr("selected").is_some()).collect::<Vec<_>>();
+
+                    if selected.is_empty() {
+                        // 何も選ばれていなければ最初の選択肢が送られる
+                        if control.attr("multiple").is_none() {
+                            if let Some(first) = options.first() {
+                                fields.push((name.to_string(), option_value(first)));
+                            }
+                        }
+                    } else {
+                        fields.extend(selected.into_iter().map(|o| (name.to_string(), option_value(o))));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        fields
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.fields.iter().filter(move |(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// 既にあれば最初のものを書き換え、なければ末尾に足す。
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.fields.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => self.fields.push((name.to_string(), value)),
+        }
+    }
+
+    /// 同じ名前のフィールドをすべて`values`で置き換える。位置は最初のものに揃える。
+    pub fn set_all(&mut self, name: &str, values: impl IntoIterator<Item = String>) {
+        let position = self.fields.iter().position(|(n, _)| n == name).unwrap_or(self.fields.len());
+        self.fields.retain(|(n, _)| n != name);
+        let position = position.min(self.fields.len());
+        let new_fields = values.into_iter().map(|v| (name.to_string(), v)).collect::<Vec<_>>();
+        self.fields.splice(position..position, new_fields);
+    }
+
+    /// 送信先。ページには本番の絶対URLが書かれているので、`canonical`のURLは`base`の同じパスに読み替える。
+    /// `base`と違うオリジンへはセッションのcookieを送らないように`None`を返す。
+    pub fn target(&self, base: &Url, canonical: &Url) -> Option<Url> {
+        let url = base.join(&self.action).ok()?;
+        let url = if url.origin() == canonical.origin() {
+            let mut rebased = base.clone();
+            rebased.set_path(url.path());
+            rebased.set_query(url.query());
+            rebased
+        } else {
+            url
+        };
+
+        (url.origin() == base.origin()).then_some(url)
+    }
+
+    /// `<input type="checkbox">`の前に置かれた同名の`hidden`を含めて書き換える。
+    pub fn set_checkbox(&mut self, name: &str, checked: bool, checked_value: &str, unchecked_value: &str) {
+        let value = if checked { checked_value } else { unchecked_value };
+        self.set_all(name, [value.to_string()]);
+    }
+}
+
+/// 検証に失敗したフォームを描き直したページにあるエラーメッセージ
+pub fn error_messages(html: &str) -> Vec<String> {
+    let document = Document::from(html);
+    let blocks = Attr("id", "error_explanation").descendant(Name("li"))
+        .or(Class("error-message"))
+        .or(Class("alert-danger"));
+
+    document.find(blocks)
+        .map(|x| x.text().split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::Url;
+    use crate::form::{error_messages, HtmlForm};
+
+    const PAGE: &str = r#"<html><body>
+        <form action="/search"><input name="q" value="x"></form>
+        <form action="/items/123" method="post">
+            <input type="hidden" name="_method" value="patch">
+            <input type="hidden" name="authenticity_token" value="token">
+            <input type="text" name="item[name]" value="Name &amp; more">
+            <textarea name="item[description]">line 1
+line 2</textarea>
+            <input type="hidden" name="item[adult]" value="0">
+            <input type="checkbox" name="item[adult]" value="1">
+            <input type="radio" name="item[state]" value="draft">
+            <input type="radio" name="item[state]" value="public" checked>
+            <select name="item[category_id]"><option value="1">a</option><option value="2" selected>b</option></select>
+            <select name="item[other]"><option>first</option></select>
+            <input type="text" name="ignored" disabled value="x">
+            <input type="file" name="file">
+            <input type="submit" name="commit" value="save">
+        </form>
+    </body></html>"#;
+
+    #[test]
+    fn find_with_field() {
+        let page = r#"<form action="/messages/901/read"><input name="_method" value="patch"></form>
+            <form action="/messages/901/replies"><textarea name="message[body]"></textarea></form>"#;
+        assert_eq!(HtmlForm::find_with_field(page, "message[body]").unwrap().action, "/messages/901/replies");
+        assert_eq!(HtmlForm::find_with_field(page, "message[subject]"), None);
+    }
+
+    #[test]
+    fn parse() {
+        let form = HtmlForm::find(PAGE, "/items/123").unwrap();
+        assert_eq!(form.action, "/items/123");
+        assert_eq!(form.fields, [
+            ("_method", "patch"),
+            ("authenticity_token", "token"),
+            ("item[name]", "Name & more"),
+            ("item[description]", "line 1\nline 2"),
+            ("item[adult]", "0"),
+            ("item[state]", "public"),
+            ("item[category_id]", "2"),
+            ("item[other]", "first"),
+        ].map(|(n, v)| (n.to_string(), v.to_string())));
+    }
+
+    #[test]
+    fn modify() {
+        let mut form = HtmlForm::find(PAGE, "/items/123").unwrap();
+        form.set("item[name]", "new");
+        form.set_checkbox("item[adult]", true, "1", "0");
+        form.set_all("item[tags][]", ["a".to_string(), "b".to_string()]);
+        assert_eq!(form.get("item[name]"), Some("new"));
+        assert_eq!(form.get_all("item[adult]").collect::<Vec<_>>(), ["1"]);
+        assert_eq!(form.get_all("item[tags][]").collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn target() {
+        let canonical = Url::parse("https://manage.booth.pm").unwrap();
+        let local = Url::parse("http://127.0.0.1:8766").unwrap();
+        let form = |action: &str| HtmlForm { action: action.to_string(), fields: vec![] };
+
+        assert_eq!(form("/items/1").target(&canonical, &canonical).unwrap().as_str(), "https://manage.booth.pm/items/1");
+        assert_eq!(form("https://manage.booth.pm/items/1?a=b").target(&local, &canonical).unwrap().as_str(), "http://127.0.0.1:8766/items/1?a=b");
+        assert_eq!(form("/items/1").target(&local, &canonical).unwrap().as_str(), "http://127.0.0.1:8766/items/1");
+        assert!(form("https://evil.example/items/1").target(&canonical, &canonical).is_none());
+        assert!(form("//evil.example/items/1").target(&canonical, &canonical).is_none());
+    }
+
+    #[test]
+    fn rejected_form() {
+        let page = r#"<div id="error_explanation"><ul><li>価格は100以上の値にしてください</li><li>名前を入力してください</li></ul></div>
+            <p class="error-message">
+                在庫は0以上の値にしてください
+            </p>"#;
+        assert_eq!(error_messages(page), ["価格は100以上の値にしてください", "名前を入力してください", "在庫は0以上の値にしてください"]);
+        assert!(error_messages(PAGE).is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+use std::path::{Path, PathBuf};
+use imagesize::ImageType;
+use thiserror::Error;
+use crate::booth::{GalleryImage, ImageId, ItemId};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::pretty_size::pretty_size;
+use crate::prompt::confirm;
+
+/// アップロードしてから断られる前に手元で弾くための制限
+const MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
+const MIN_SIDE: usize = 100;
+const MAX_SIDE: usize = 10000;
+
+#[derive(Error, Debug)]
+pub enum GalleryError {
+    #[error("{}", message!("error.gallery.unsupported-format", path = .0.display()))]
+    UnsupportedFormat(PathBuf),
+    #[error("{}", message!("error.gallery.too-large", path = .path.display(), size = pretty_size(*.size), max = pretty_size(MAX_FILE_SIZE)))]
+    TooLarge {
+        path: PathBuf,
+        size: usize,
+    },
+    #[error("{}", message!("error.gallery.dimensions", path = .path.display(), width = .width, height = .height, min = MIN_SIDE, max = MAX_SIDE))]
+    Dimensions {
+        path: PathBuf,
+        width: usize,
+        height: usize,
+    },
+    #[error("{}", message!("error.gallery.duplicate-name", name = .0))]
+    DuplicateName(String),
+}
+
+/// 検証済みのローカルの画像
+struct LocalImage {
+    name: String,
+    content: Vec<u8>,
+}
+
+fn validate(path: &Path) -> Result<LocalImage, ExecutionError> {
+    let name = path.file_name()
+        .and_then(|x| x.to_str())
+        .ok_or_else(|| ExecutionError::CommandLineArgumentValidation(message!("validation.non-utf8-name", path = path.display())))?
+        .to_string();
+    let content = std::fs::read(path)?;
+    check(path, &content)?;
+
+    Ok(LocalImage { name, content })
+}
+
+fn check(path: &Path, content: &[u8]) -> Result<(), GalleryError> {
+    let unsupported = || GalleryError::UnsupportedFormat(path.to_path_buf());
+    match imagesize::image_type(content).map_err(|_| unsupported())? {
+        ImageType::Jpeg | ImageType::Png | ImageType::Gif => {}
+        _ => return Err(unsupported()),
+    }
+
+    if content.len() > MAX_FILE_SIZE {
+        return Err(GalleryError::TooLarge { path: path.to_path_buf(), size: content.len() })
+    }
+
+    let size = imagesize::blob_size(content).map_err(|_| unsupported())?;
+    let in_range = |side: usize| (MIN_SIDE..=MAX_SIDE).contains(&side);
+    if !in_range(size.width) || !in_range(size.height) {
+        return Err(GalleryError::Dimensions { path: path.to_path_buf(), width: size.width, height: size.height })
+    }
+
+    Ok(())
+}
+
+/// 全部を検証してから読み込む。一枚でも不正なら何もアップロードしない。
+fn validate_all(paths: &[PathBuf]) -> Result<Vec<LocalImage>, ExecutionError> {
+    let images = paths.iter().map(|path| validate(path)).collect::<Result<Vec<_>, _>>()?;
+    for (i, image) in images.iter().enumerate() {
+        if images[..i].iter().any(|x| x.name == image.name) {
+            return Err(GalleryError::DuplicateName(image.name.clone()).into())
+        }
+    }
+
+    Ok(images)
+}
+
+/// `wanted`のそれぞれに対応する`remote`の位置。同じ名前の画像が複数あれば前のものから一つずつ対応させる。
+fn pair(remote: &[GalleryImage], wanted: &[&str]) -> Vec<Option<usize>> {
+    let mut used = vec![false; remote.len()];
+    wanted.iter()
+        .map(|name| {
+            let i = (0..remote.len()).find(|i| !used[*i] && remote[*i].name == *name)?;
+            used[i] = true;
+            Some(i)
+        })
+        .collect()
+}
+
+/// `wanted`の順に並べ、`wanted`にない画像は`prune`しなければ後ろに元の順で残す。
+/// 同じ名前の画像が複数あれば前のものから一つずつ対応させ、余ったものは消さずに後ろに残す。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn arrange(remote: &[GalleryImage], wanted: &[&str], prune: bool) -> (Vec<ImageId>, Vec<ImageId>) {
+    let mut used = vec![false; remote.len()];
+    let mut order = vec![];
+    for i in pair(remote, wanted).into_iter().flatten() {
+        used[i] = true;
+        order.push(remote[i].id);
+    }
+
+    let mut pruned = vec![];
+    for (image, _) in remote.iter().zip(used).filter(|(_, used)| !used) {
+        if prune && !wanted.contains(&image.name.as_str()) {
+            pruned.push(image.id);
+        } else {
+            order.push(image.id);
+        }
+    }
+
+    (order, pruned)
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn list(client: &ManageClient, booth_item_id: ItemId) -> Result<(), ExecutionError> {
+    let gallery = client.gallery(booth_item_id).await?;
+    println!("{}", message!("gallery.count", item = booth_item_id, count = gallery.images.len()));
+    for (position, image) in gallery.images.iter().enumerate() {
+        println!("  {position}. #{id} {name} {url}", position = position + 1, id = image.id, name = image.name, url = image.url);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn upload(client: &ManageClient, booth_item_id: ItemId, paths: &[PathBuf]) -> Result<(), ExecutionError> {
+    let images = validate_all(paths)?;
+    let csrf_token = client.csrf_token(booth_item_id).await?;
+    for image in images {
+        println!("{}", message!("gallery.uploading", name = image.name));
+        let uploaded = client.upload_image(booth_item_id, &image.name, image.content, &csrf_token).await?;
+        println!("{}", message!("gallery.uploaded", name = uploaded.name, id = uploaded.id));
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn delete(client: &ManageClient, booth_item_id: ItemId, images: &[String]) -> Result<(), ExecutionError> {
+    let gallery = client.gallery(booth_item_id).await?;
+    let targets = images.iter()
+        .map(|x| {
+            gallery.images.iter()
+                .find(|image| image.id.to_string() == *x || image.name == *x)
+                .ok_or_else(|| ExecutionError::CommandLineArgumentValidation(message!("validation.unknown-image", image = x)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let csrf_token = client.csrf_token(booth_item_id).await?;
+    for image in targets {
+        println!("{}", message!("gallery.deleting", name = image.name));
+        client.delete_image(booth_item_id, image.id, &csrf_token).await?;
+    }
+
+    Ok(())
+}
+
+/// ギャラリーを`paths`の順にする。名前が一致する画像がなければアップロードし、
+/// 名前が一致しても中身が違えば新しい画像をアップロードして古い画像と入れ替える。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn arrange_as(client: &ManageClient, booth_item_id: ItemId, paths: &[PathBuf], prune: bool, yes: bool) -> Result<(), ExecutionError> {
+    let images = validate_all(paths)?;
+    let mut remote = client.gallery(booth_item_id).await?.images;
+    let names = images.iter().map(|x| x.name.clone()).collect::<Vec<_>>();
+    let wanted = names.iter().map(String::as_str).collect::<Vec<_>>();
+    let (_, pruned) = arrange(&remote, &wanted, prune);
+
+    if !pruned.is_empty() && !yes && !confirm(&message!("gallery.confirm-prune", count = pruned.len()))? {
+        println!("{}", message!("confirm.aborted"));
+        return Ok(())
+    }
+
+    let csrf_token = client.csrf_token(booth_item_id).await?;
+    let mut replaced = vec![];
+    for (image, paired) in images.into_iter().zip(pair(&remote, &wanted)) {
+        let Some(i) = paired else {
+            println!("{}", message!("gallery.uploading", name = image.name));
+            remote.push(client.upload_image(booth_item_id, &image.name, image.content, &csrf_token).await?);
+            continue
+        };
+        if client.image_content(&remote[i]).await? == image.content {
+            continue
+        }
+
+        // 元の画像の位置に置くため、一覧の同じ場所を差し替える
+        println!("{}", message!("gallery.replacing", name = image.name));
+        let uploaded = client.upload_image(booth_item_id, &image.name, image.content, &csrf_token).await?;
+        replaced.push(std::mem::replace(&mut remote[i], uploaded));
+    }
+
+    for old in replaced {
+        println!("{}", message!("gallery.deleting", name = old.name));
+        client.delete_image(booth_item_id, old.id, &csrf_token).await?;
+    }
+
+    let (order, pruned) = arrange(&remote, &wanted, prune);
+    for id in pruned {
+        let name = remote.iter().find(|x| x.id == id).map_or_else(String::new, |x| x.name.clone());
+        println!("{}", message!("gallery.deleting", name = name));
+        client.delete_image(booth_item_id, id, &csrf_token).await?;
+    }
+
+    client.reorder_images(booth_item_id, &order, &csrf_token).await?;
+    println!("{}", message!("gallery.arranged", item = booth_item_id, count = order.len()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use crate::fixture::image;
+    use crate::gallery::{arrange, check, pair, GalleryError};
+
+    /// 幅と高さだけを持つPNGのヘッダー
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend(width.to_be_bytes());
+        png.extend(height.to_be_bytes());
+        png.extend([8, 6, 0, 0, 0, 0, 0, 0, 0]);
+        png
+    }
+
+    #[test]
+    fn validation() {
+        let path = Path::new("a.png");
+        assert!(check(path, &png(1920, 1080)).is_ok());
+        assert!(matches!(check(path, &png(1920, 50)), Err(GalleryError::Dimensions { height: 50, .. })));
+        assert!(matches!(check(path, &png(20000, 1080)), Err(GalleryError::Dimensions { width: 20000, .. })));
+        assert!(matches!(check(path, b"not an image"), Err(GalleryError::UnsupportedFormat(_))));
+        assert!(matches!(check(path, b"BM\0\0\0\0\0\0\0\0"), Err(GalleryError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn arrangement() {
+        let images = [image(1, "a.png"), image(2, "b.png"), image(3, "c.png")];
+        let ids = |v: Vec<crate::booth::ImageId>| v.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        let (order, pruned) = arrange(&images, &["c.png", "a.png"], false);
+        assert_eq!((ids(order), ids(pruned)), (vec!["3".to_string(), "1".to_string(), "2".to_string()], vec![]));
+
+        let (order, pruned) = arrange(&images, &["c.png", "new.png", "a.png"], true);
+        assert_eq!((ids(order), ids(pruned)), (vec!["3".to_string(), "1".to_string()], vec!["2".to_string()]));
+
+        // 同名の画像も並べ替えから漏らさない
+        let images = [image(1, "a.png"), image(2, "a.png"), image(3, "b.png")];
+        let (order, pruned) = arrange(&images, &["b.png", "a.png"], true);
+        assert_eq!((ids(order), ids(pruned)), (vec!["3".to_string(), "1".to_string(), "2".to_string()], vec![]));
+        let (order, pruned) = arrange(&images, &["a.png", "a.png"], true);
+        assert_eq!((ids(order), ids(pruned)), (vec!["1".to_string(), "2".to_string()], vec!["3".to_string()]));
+    }
+
+    #[test]
+    fn pairing() {
+        let images = [image(1, "a.png"), image(2, "b.png"), image(3, "a.png")];
+        assert_eq!(pair(&images, &["a.png", "new.png", "a.png", "a.png", "b.png"]), [Some(0), None, Some(2), None, Some(1)]);
+    }
+}
@@ -0,0 +1,453 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use chrono::{DateTime, FixedOffset, Utc};
+use thiserror::Error;
+use crate::booth::{AgeRestriction, FileId, ItemDraft, ItemExport, ItemId, PublishState, UploadResult, UploadedObject};
+use crate::{gallery, ExecutionError};
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::pretty_size::pretty_size;
+use crate::prompt::confirm;
+
+/// `--price`の値。`<バリエーションのIDか名前>=<価格>`、または全バリエーションに適用する`<価格>`。
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct VariationPrice {
+    pub variation: Option<String>,
+    pub price: u32,
+}
+
+#[derive(Error, Debug)]
+pub enum ParseVariationPriceError {
+    #[error("{}", message!("error.item.invalid-price", price = .0))]
+    InvalidPrice(String),
+}
+
+impl FromStr for VariationPrice {
+    type Err = ParseVariationPriceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (variation, price) = s.rsplit_once('=').map_or((None, s), |(v, p)| (Some(v.trim().to_string()), p));
+        let price = price.trim().parse().map_err(|_| ParseVariationPriceError::InvalidPrice(price.to_string()))?;
+
+        Ok(Self { variation, price })
+    }
+}
+
+/// `item set`で変更する項目。`None`の項目はそのままにする。
+#[derive(clap::Args)]
+pub struct ItemChanges {
+    #[clap(long)]
+    name: Option<String>,
+    #[clap(long, conflicts_with = "description_file")]
+    description: Option<String>,
+    #[clap(long)]
+    description_file: Option<PathBuf>,
+    #[clap(long, value_delimiter = ',')]
+    tags: Option<Vec<String>>,
+    #[clap(long)]
+    category: Option<String>,
+    #[clap(long)]
+    price: Vec<VariationPrice>,
+    #[clap(long)]
+    age_restriction: Option<AgeRestriction>,
+    #[clap(long)]
+    state: Option<PublishState>,
+}
+
+impl ItemChanges {
+    fn apply(self, draft: &mut ItemDraft) -> Result<(), ExecutionError> {
+        if let Some(name) = self.name {
+            draft.name = name;
+        }
+        if let Some(path) = self.description_file {
+            draft.description = std::fs::read_to_string(path)?;
+        }
+        if let Some(description) = self.description {
+            draft.description = description;
+        }
+        if let Some(tags) = self.tags {
+            draft.tags = tags.into_iter().map(|x| x.trim().to_string()).filter(|x| !x.is_empty()).collect();
+        }
+        if let Some(category) = self.category {
+            draft.category_id = Some(category);
+        }
+        for VariationPrice { variation, price } in self.price {
+            match variation {
+                Some(variation) => draft.variation_mut(&variation)?.price = price,
+                None => draft.variations.iter_mut().for_each(|v| v.price = price),
+            }
+        }
+        if let Some(age_restriction) = self.age_restriction {
+            draft.age_restriction = age_restriction;
+        }
+        if let Some(state) = self.state {
+            draft.state = state;
+        }
+
+        Ok(())
+    }
+}
+
+fn print_draft(draft: &ItemDraft) {
+    println!("{}", message!("item.name", name = draft.name));
+    println!("{}", message!("item.state", state = draft.state));
+    println!("{}", message!("item.age-restriction", age_restriction = draft.age_restriction));
+    println!("{}", message!("item.category", category = draft.category_id.as_deref().unwrap_or("-")));
+    println!("{}", message!("item.tags", tags = draft.tags.join(", ")));
+    println!("{}", message!("item.variations"));
+    for variation in &draft.variations {
+        let id = variation.id.map_or_else(|| "-".to_string(), |id| id.to_string());
+        let kind = variation.kind.as_deref().unwrap_or("-");
+        println!("  #{id} {name} ({kind}): ¥{price}", name = variation.name, price = variation.price);
+    }
+    println!("{}", message!("item.description"));
+    println!("{}", draft.description);
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn get(client: &ManageClient, booth_item_id: ItemId, json: bool) -> Result<(), ExecutionError> {
+    let (form, _) = client.edit_form(booth_item_id).await?;
+    let draft = ItemDraft::from_form(&form)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&draft)?);
+    } else {
+        print_draft(&draft);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn set(client: &ManageClient, booth_item_id: ItemId, changes: ItemChanges) -> Result<(), ExecutionError> {
+    let (mut form, csrf_token) = client.edit_form(booth_item_id).await?;
+    let before = ItemDraft::from_form(&form)?;
+    let mut after = before.clone();
+    changes.apply(&mut after)?;
+
+    if before == after {
+        println!("{}", message!("item.unchanged", item = booth_item_id));
+        return Ok(())
+    }
+
+    after.apply_to(&mut form);
+    client.submit_form(&form, &csrf_token).await?;
+    println!("{}", message!("item.updated", item = booth_item_id));
+
+    Ok(())
+}
+
+/// `--at`の値。タイムゾーンを省略した日時は受け付けない。
+pub fn parse_rfc3339(s: &str) -> Result<DateTime<FixedOffset>, String> {
+    DateTime::parse_from_rfc3339(s).map_err(|e| message!("error.item.invalid-time", time = s, error = e))
+}
+
+async fn change_state(client: &ManageClient, booth_item_id: ItemId, state: PublishState, reservation: Option<DateTime<FixedOffset>>) -> Result<(), ExecutionError> {
+    let (mut form, csrf_token) = client.edit_form(booth_item_id).await?;
+    let mut draft = ItemDraft::from_form(&form)?;
+    draft.state = state;
+    draft.apply_to(&mut form);
+    ItemDraft::reserve_publication(&mut form, reservation);
+    client.submit_form(&form, &csrf_token).await
+}
+
+/// `at`が未来なら、編集フォームに公開予約の欄があればそれを使い、なければその時刻まで待ってから公開する。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn publish(client: &ManageClient, booth_item_id: ItemId, at: Option<DateTime<FixedOffset>>) -> Result<(), ExecutionError> {
+    let wait = at.and_then(|at| (at.with_timezone(&Utc) - Utc::now()).to_std().ok().map(|wait| (at, wait)));
+    let Some((at, wait)) = wait else {
+        change_state(client, booth_item_id, PublishState::Public, None).await?;
+        println!("{}", message!("item.published", item = booth_item_id));
+        return Ok(())
+    };
+
+    let (mut form, csrf_token) = client.edit_form(booth_item_id).await?;
+    if ItemDraft::reserve_publication(&mut form, Some(at)) {
+        client.submit_form(&form, &csrf_token).await?;
+        println!("{}", message!("item.reserved", item = booth_item_id, at = at.to_rfc3339()));
+        return Ok(())
+    }
+
+    println!("{}", message!("item.waiting", item = booth_item_id, at = at.to_rfc3339()));
+    tokio::time::sleep(wait).await;
+    change_state(client, booth_item_id, PublishState::Public, None).await?;
+    println!("{}", message!("item.published", item = booth_item_id));
+
+    Ok(())
+}
+
+/// 非公開にする。公開予約があれば取り消す。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn unpublish(client: &ManageClient, booth_item_id: ItemId) -> Result<(), ExecutionError> {
+    change_state(client, booth_item_id, PublishState::Private, None).await?;
+    println!("{}", message!("item.unpublished", item = booth_item_id));
+
+    Ok(())
+}
+
+/// 項目ごとの変更。`item import`で書き込む前に表示する
+#[derive(Eq, PartialEq, Debug)]
+struct FieldChange {
+    field: String,
+    before: String,
+    after: String,
+}
+
+fn diff(before: &ItemDraft, after: &ItemDraft) -> Vec<FieldChange> {
+    let mut changes = vec![];
+    let mut compare = |field: String, before: String, after: String| {
+        if before != after {
+            changes.push(FieldChange { field, before, after });
+        }
+    };
+    let ids = |ids: &[FileId]| ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+
+    compare("name".to_string(), before.name.clone(), after.name.clone());
+    compare("description".to_string(), before.description.clone(), after.description.clone());
+    compare("tags".to_string(), before.tags.join(", "), after.tags.join(", "));
+    compare("category".to_string(), before.category_id.clone().unwrap_or_default(), after.category_id.clone().unwrap_or_default());
+    compare("age_restriction".to_string(), before.age_restriction.to_string(), after.age_restriction.to_string());
+    compare("state".to_string(), before.state.to_string(), after.state.to_string());
+    // restoreはバリエーションを増減させないので、同じ位置どうしを比べる
+    for (b, a) in before.variations.iter().zip(&after.variations) {
+        compare(format!("variations[{name}].name", name = b.name), b.name.clone(), a.name.clone());
+        compare(format!("variations[{name}].price", name = b.name), b.price.to_string(), a.price.to_string());
+        compare(format!("variations[{name}].downloadable_ids", name = b.name), ids(&b.downloadable_ids), ids(&a.downloadable_ids));
+    }
+
+    changes
+}
+
+fn print_diff(changes: &[FieldChange]) {
+    for change in changes {
+        println!("~ {field}", field = change.field);
+        for line in change.before.lines() {
+            println!("  - {line}");
+        }
+        for line in change.after.lines() {
+            println!("  + {line}");
+        }
+    }
+}
+
+/// 書き出したファイルを名前で今のファイルに対応させる。ファイルのIDはアイテムごとに振られ、上げ直すたびに変わる。
+/// 同じ名前が複数あればサイズも同じものを優先する。対応するものがなかったファイルも返す。
+fn match_downloadables<'a>(exported: &'a [UploadedObject], remote: &[UploadedObject]) -> (Vec<(FileId, FileId)>, Vec<&'a UploadedObject>) {
+    let mut used = vec![false; remote.len()];
+    let mut mapping = vec![];
+    let mut missing = vec![];
+    for file in exported {
+        let candidates = || (0..remote.len()).filter(|i| !used[*i] && remote[*i].name == file.name);
+        match candidates().find(|i| remote[*i].file_size == file.file_size).or_else(|| candidates().next()) {
+            Some(i) => {
+                used[i] = true;
+                mapping.push((file.id, remote[i].id));
+            }
+            None => missing.push(file),
+        }
+    }
+
+    (mapping, missing)
+}
+
+async fn export_of(client: &ManageClient, booth_item_id: ItemId) -> Result<ItemExport, ExecutionError> {
+    let (form, _) = client.edit_form(booth_item_id).await?;
+
+    Ok(ItemExport {
+        item: ItemDraft::from_form(&form)?,
+        images: client.gallery(booth_item_id).await?.images,
+        downloadables: client.downloadables(booth_item_id).await?.files,
+    })
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn export(client: &ManageClient, booth_item_id: ItemId) -> Result<(), ExecutionError> {
+    let exported = export_of(client, booth_item_id).await?;
+    println!("{}", serde_json::to_string_pretty(&exported)?);
+
+    Ok(())
+}
+
+/// `item create`の元にするもの
+pub enum ItemTemplate {
+    File(PathBuf),
+    Clone(ItemId),
+}
+
+/// 下書きのアイテムを作って`template`の内容を書き込み、IDを標準出力に出す。進捗は標準エラー出力に出す。
+/// ダウンロードファイルは`downloadables_dir`に同じ名前のファイルがあればアップロードして、同じバリエーションに紐付ける。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn create(client: &ManageClient, template: ItemTemplate, downloadables_dir: Option<&Path>) -> Result<(), ExecutionError> {
+    let template = match template {
+        ItemTemplate::File(path) => serde_json::from_reader::<_, ItemExport>(std::io::BufReader::new(std::fs::File::open(path)?))?,
+        ItemTemplate::Clone(id) => export_of(client, id).await?,
+    };
+
+    let booth_item_id = client.create_item().await?;
+    eprintln!("{}", message!("item.create.created", item = booth_item_id));
+    let (mut form, csrf_token) = client.edit_form(booth_item_id).await?;
+
+    let mut uploaded = vec![];
+    for file in &template.downloadables {
+        let path = downloadables_dir.map(|dir| dir.join(&file.name)).filter(|path| path.is_file());
+        let Some(path) = path else {
+            eprintln!("{}", message!("item.import.missing-downloadable", name = file.name, size = pretty_size(file.file_size)));
+            continue
+        };
+
+        eprintln!("{}", message!("sync.uploading", name = file.name));
+        match client.upload(booth_item_id, &path, &csrf_token).await? {
+            UploadResult::Ok { uploaded_file, .. } => uploaded.push((file.id, uploaded_file.id)),
+            UploadResult::Err(error) => return Err(error.into()),
+        }
+    }
+
+    let mut draft = ItemDraft::from_form(&form)?;
+    draft.restore(&template.item, &uploaded);
+    let mut variations = template.item.variations.clone();
+    for variation in &mut variations {
+        variation.remap_downloadables(&uploaded);
+    }
+    draft.replace_variations(&variations);
+    draft.state = PublishState::Draft;
+    draft.apply_to(&mut form);
+    client.submit_form(&form, &csrf_token).await?;
+
+    for image in &template.images {
+        eprintln!("{}", message!("item.import.missing-image", name = image.name));
+    }
+    println!("{booth_item_id}");
+
+    Ok(())
+}
+
+/// `item export`で書き出したファイルを書き戻す。画像とダウンロードファイルは並べ替えと確認だけを行う。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn import(client: &ManageClient, booth_item_id: ItemId, file: &Path, yes: bool, dry_run: bool) -> Result<(), ExecutionError> {
+    let exported = serde_json::from_reader::<_, ItemExport>(std::io::BufReader::new(std::fs::File::open(file)?))?;
+    let (mut form, csrf_token) = client.edit_form(booth_item_id).await?;
+    let before = ItemDraft::from_form(&form)?;
+    let remote_files = client.downloadables(booth_item_id).await?.files;
+    let (files, missing) = match_downloadables(&exported.downloadables, &remote_files);
+    for missing in missing {
+        println!("{}", message!("item.import.missing-downloadable", name = missing.name, size = pretty_size(missing.file_size)));
+    }
+
+    let mut after = before.clone();
+    for name in after.restore(&exported.item, &files) {
+        println!("{}", message!("item.import.unknown-variation", variation = name));
+    }
+
+    let images = client.gallery(booth_item_id).await?.images;
+    let wanted = exported.images.iter().map(|x| x.name.as_str()).collect::<Vec<_>>();
+    for missing in wanted.iter().filter(|name| !images.iter().any(|x| x.name == **name)) {
+        println!("{}", message!("item.import.missing-image", name = missing));
+    }
+    let (order, _) = gallery::arrange(&images, &wanted, false);
+    let reorder = order != images.iter().map(|x| x.id).collect::<Vec<_>>();
+
+    let changes = diff(&before, &after);
+    print_diff(&changes);
+    if reorder {
+        println!("~ images");
+        println!("  - {}", images.iter().map(|x| x.name.as_str()).collect::<Vec<_>>().join(", "));
+        println!("  + {}", order.iter().filter_map(|id| images.iter().find(|x| x.id == *id)).map(|x| x.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+
+    if changes.is_empty() && !reorder {
+        println!("{}", message!("item.unchanged", item = booth_item_id));
+        return Ok(())
+    }
+
+    if dry_run {
+        return Ok(())
+    }
+
+    if !yes && !confirm(&message!("item.import.confirm", item = booth_item_id))? {
+        println!("{}", message!("confirm.aborted"));
+        return Ok(())
+    }
+
+    if !changes.is_empty() {
+        after.apply_to(&mut form);
+        client.submit_form(&form, &csrf_token).await?;
+    }
+    if reorder {
+        client.reorder_images(booth_item_id, &order, &csrf_token).await?;
+    }
+    println!("{}", message!("item.updated", item = booth_item_id));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::booth::{ItemDraft, PublishState, VariationDraft};
+    use crate::fixture::{self, uploaded, variation};
+    use crate::item::{diff, match_downloadables, parse_rfc3339, FieldChange, VariationPrice};
+
+    #[test]
+    fn parse_variation_price() {
+        assert_eq!("Pro=2000".parse::<VariationPrice>().unwrap(), VariationPrice { variation: Some("Pro".to_string()), price: 2000 });
+        assert_eq!("a=b=100".parse::<VariationPrice>().unwrap(), VariationPrice { variation: Some("a=b".to_string()), price: 100 });
+        assert_eq!("500".parse::<VariationPrice>().unwrap(), VariationPrice { variation: None, price: 500 });
+        assert!("Pro=free".parse::<VariationPrice>().is_err());
+    }
+
+    #[test]
+    fn parse_time() {
+        assert_eq!(parse_rfc3339("2026-01-01T09:00:00+09:00").unwrap(), parse_rfc3339("2026-01-01T00:00:00Z").unwrap());
+        assert!(parse_rfc3339("2026-01-01 09:00").is_err());
+    }
+
+    fn draft(name: &str, price: u32) -> ItemDraft {
+        ItemDraft {
+            name: name.to_string(),
+            description: "v1.0.0".to_string(),
+            tags: vec!["VRChat".to_string()],
+            state: PublishState::Draft,
+            ..fixture::draft(vec![variation(0, 11, "Basic", price)])
+        }
+    }
+
+    #[test]
+    fn restore_and_diff() {
+        let current = draft("Sample", 500);
+        let mut exported = draft("Sample (old)", 300);
+        exported.variations[0].index = String::new();
+        exported.variations.push(VariationDraft { id: Some(99), name: "Removed".to_string(), ..exported.variations[0].clone() });
+
+        let mut restored = current.clone();
+        assert_eq!(restored.restore(&exported, &[]), ["Removed"]);
+        assert_eq!(restored.variations.len(), 1);
+        assert_eq!(restored.variations[0].index, "0");
+
+        let change = |field: &str, before: &str, after: &str| FieldChange { field: field.to_string(), before: before.to_string(), after: after.to_string() };
+        assert_eq!(diff(&current, &restored), [
+            change("name", "Sample", "Sample (old)"),
+            change("variations[Basic].price", "500", "300"),
+        ]);
+        assert!(diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn restore_downloadables_by_name() {
+        // 別のアイテムか、同期で上げ直した後なのでIDが違う
+        let exported_files = [uploaded(101, "basic.zip", 10), uploaded(102, "manual.pdf", 20)];
+        let remote_files = [uploaded(7, "manual.pdf", 20), uploaded(8, "basic.zip", 11), uploaded(9, "extra.zip", 30)];
+        let (mapping, missing) = match_downloadables(&exported_files, &remote_files);
+        assert!(missing.is_empty());
+
+        let mut exported = draft("Sample", 500);
+        exported.variations[0].downloadable_ids = vec![exported_files[0].id, exported_files[1].id];
+        let mut current = draft("Sample", 500);
+        current.variations[0].downloadable_ids = vec![remote_files[2].id];
+        current.restore(&exported, &mapping);
+        assert_eq!(current.variations[0].downloadable_ids, [remote_files[1].id, remote_files[0].id]);
+
+        let (mapping, missing) = match_downloadables(&exported_files, &remote_files[..1]);
+        assert_eq!(missing.iter().map(|x| x.name.as_str()).collect::<Vec<_>>(), ["basic.zip"]);
+        current.restore(&exported, &mapping);
+        assert_eq!(current.variations[0].downloadable_ids, [remote_files[0].id]);
+    }
+}
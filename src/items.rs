@@ -0,0 +1,91 @@
+use crate::booth::{ItemId, ItemRef, ItemSummary, PublishState};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::quota::wildcard_match;
+
+fn filter(items: Vec<ItemSummary>, status: Option<PublishState>, name_match: Option<&str>) -> Vec<ItemSummary> {
+    items.into_iter()
+        .filter(|x| status.is_none_or(|status| x.state == status))
+        .filter(|x| name_match.is_none_or(|pattern| wildcard_match(pattern, &x.name)))
+        .collect()
+}
+
+fn price_range(item: &ItemSummary) -> String {
+    match (item.min_price, item.max_price) {
+        (Some(min), Some(max)) if min != max => format!("¥{min}-¥{max}"),
+        (Some(price), _) => format!("¥{price}"),
+        _ => "-".to_string(),
+    }
+}
+
+/// `name:<名前>`はショップのアイテム一覧から完全一致で探す。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn resolve(client: &ManageClient, item: &ItemRef) -> Result<ItemId, ExecutionError> {
+    let name = match item {
+        ItemRef::Id(id) => return Ok(*id),
+        ItemRef::Name(name) => name,
+    };
+
+    let found = client.items().await?.into_iter().filter(|x| x.name == *name).collect::<Vec<_>>();
+    match found.as_slice() {
+        [item] => Ok(item.id),
+        [] => Err(ExecutionError::ItemNotFound(name.clone())),
+        _ => Err(ExecutionError::AmbiguousItem { name: name.clone(), count: found.len() }),
+    }
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn list(client: &ManageClient, status: Option<PublishState>, name_match: Option<&str>, json: bool) -> Result<(), ExecutionError> {
+    let items = filter(client.items().await?, status, name_match);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(())
+    }
+
+    for item in &items {
+        println!("{}", message!(
+            "items.row",
+            id = item.id,
+            state = item.state,
+            name = item.name,
+            price = price_range(item),
+            variations = item.variation_count,
+            downloadables = item.downloadable_count,
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::booth::{ItemId, ItemSummary, PublishState};
+    use crate::items::{filter, price_range};
+
+    fn item(id: u32, name: &str, state: PublishState, prices: (Option<u32>, Option<u32>)) -> ItemSummary {
+        ItemSummary { id: ItemId::new(id).unwrap(), name: name.to_string(), state, min_price: prices.0, max_price: prices.1, variation_count: 0, downloadable_count: 0 }
+    }
+
+    #[test]
+    fn filters() {
+        let items = vec![
+            item(1, "Avatar A", PublishState::Public, (None, None)),
+            item(2, "Avatar B", PublishState::Draft, (None, None)),
+            item(3, "Shader", PublishState::Draft, (None, None)),
+        ];
+        let ids = |v: Vec<ItemSummary>| v.iter().map(|x| x.id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(ids(filter(items.clone(), Some(PublishState::Draft), None)), ["2", "3"]);
+        assert_eq!(ids(filter(items.clone(), None, Some("Avatar*"))), ["1", "2"]);
+        assert_eq!(ids(filter(items, Some(PublishState::Draft), Some("Avatar*"))), ["2"]);
+    }
+
+    #[test]
+    fn prices() {
+        assert_eq!(price_range(&item(1, "", PublishState::Public, (Some(500), Some(1500)))), "¥500-¥1500");
+        assert_eq!(price_range(&item(1, "", PublishState::Public, (Some(500), Some(500)))), "¥500");
+        assert_eq!(price_range(&item(1, "", PublishState::Public, (None, None))), "-");
+    }
+}
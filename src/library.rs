@@ -0,0 +1,475 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use reqwest::{Client, Method, RequestBuilder, StatusCode, Url};
+use crate::booth::{LibraryFile, LibraryItem, WishlistItem};
+use crate::library_index::{IndexedFile, LibraryIndex};
+use crate::library_layout::SharedDirs;
+use crate::{library_layout, locale, ExecutionError, LayoutArgs, SessionArgs};
+use crate::manage::USER_AGENT;
+use crate::message::message;
+
+const DEFAULT_BASE_URL: &str = "https://accounts.booth.pm";
+/// `--index`を指定しなければ、保存先のディレクトリに置く
+const INDEX_FILE_NAME: &str = ".booth-library.sqlite3";
+
+/// accounts.booth.pmにある、購入したアイテムのライブラリとほしいものリスト
+pub struct LibraryClient {
+    client: Client,
+    base_url: String,
+    baked_cookie: String,
+}
+
+/// ダウンロードした結果
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Fetched {
+    /// 同じ大きさのファイルがすでにある
+    Skipped,
+    Downloaded,
+    /// 途中まで落としたファイルの続きから
+    Resumed,
+}
+
+impl LibraryClient {
+    pub fn new(session: &SessionArgs) -> Self {
+        session.warn_deprecated();
+        let client = reqwest::ClientBuilder::new()
+            .gzip(true)
+            .build()
+            .unwrap();
+
+        // BOOTH_MANAGE_BASE_URLと同じく、テスト用のフィクスチャサーバーへ向けられるようにする
+        let base_url = std::env::var("BOOTH_ACCOUNTS_BASE_URL")
+            .map_or_else(|_| DEFAULT_BASE_URL.to_string(), |x| x.trim_end_matches('/').to_string());
+
+        Self {
+            client,
+            base_url,
+            // セッションはmanage.booth.pmと共通
+            baked_cookie: format!("_plaza_session_nktz7u={v}", v = &session.login_token),
+        }
+    }
+
+    /// 相対URLはライブラリと同じ場所にあるとみなす
+    fn url(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            format!("{base}{path}", base = &self.base_url)
+        } else {
+            path.to_string()
+        }
+    }
+
+    /// セッションの`Cookie`はBOOTHとライブラリと同じオリジンにだけ送る。
+    /// 別のホストへリダイレクトされたときは、reqwestが`Cookie`を外す
+    fn request(&self, method: Method, url: &str, accept: &str) -> RequestBuilder {
+        let builder = self.client.request(method, url)
+            .header("Accept", accept)
+            .header("Accept-Language", locale::current().accept_language())
+            .header("User-Agent", USER_AGENT);
+
+        if self.is_booth(url) {
+            builder.header("Cookie", &self.baked_cookie)
+        } else {
+            builder
+        }
+    }
+
+    fn is_booth(&self, url: &str) -> bool {
+        let Ok(url) = Url::parse(url) else {
+            return false
+        };
+        let on_booth = url.scheme() == "https" && url.host_str().is_some_and(|host| host == "booth.pm" || host.ends_with(".booth.pm"));
+
+        on_booth || Url::parse(&self.base_url).is_ok_and(|base| base.origin() == url.origin())
+    }
+
+    /// `page`は1から
+    async fn page(&self, path: &str, page: u32) -> Result<String, ExecutionError> {
+        let res = self.request(Method::GET, &self.url(&format!("{path}?page={page}")), "text/html; charset=utf-8")
+            .send()
+            .await?;
+
+        Ok(res.error_for_status()?.text().await?)
+    }
+
+    async fn items_in(&self, path: &str, gift: bool) -> Result<Vec<LibraryItem>, ExecutionError> {
+        let mut items = vec![];
+        for page in 1.. {
+            let (found, has_next) = LibraryItem::parse_page(&self.page(path, page).await?, gift)?;
+            let empty = found.is_empty();
+            items.extend(found);
+            if !has_next || empty {
+                break
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// 購入したアイテムと、ギフトとして受け取ったアイテム
+    pub async fn items(&self) -> Result<Vec<LibraryItem>, ExecutionError> {
+        let mut items = self.items_in("/library", false).await?;
+        items.extend(self.items_in("/library/gifts", true).await?);
+
+        Ok(items)
+    }
+
+    /// ほしいものリストのすべてのアイテム
+    pub async fn wishlist(&self) -> Result<Vec<WishlistItem>, ExecutionError> {
+        let mut items = vec![];
+        for page in 1.. {
+            let (found, has_next) = WishlistItem::parse_page(&self.page("/wish_lists", page).await?)?;
+            let empty = found.is_empty();
+            items.extend(found);
+            if !has_next || empty {
+                break
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// ダウンロードせずに大きさだけを調べる。分からなければ`None`
+    pub async fn size(&self, file: &LibraryFile) -> Result<Option<u64>, ExecutionError> {
+        let head = self.request(Method::HEAD, &self.url(&file.url), "*/*").send().await?;
+        // 署名付きURLはHEADを403で断ることがあるので、大きさが分からないだけとして扱う
+        if !head.status().is_success() {
+            return Ok(None)
+        }
+        // HEADの本文は空なので、`content_length()`ではなくヘッダーを見る
+        let size = head.headers()
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.parse::<u64>().ok());
+
+        Ok(size)
+    }
+
+    /// `path`へ保存する。途中まで落とした`.part`があれば`Range`で続きから取る。
+    pub async fn download(&self, file: &LibraryFile, path: &Path, size: Option<u64>) -> Result<Fetched, ExecutionError> {
+        let url = self.url(&file.url);
+        if let (Ok(metadata), Some(size)) = (std::fs::metadata(path), size) {
+            if metadata.len() == size {
+                return Ok(Fetched::Skipped)
+            }
+        }
+
+        let part = partial_path(path);
+        let mut offset = std::fs::metadata(&part).map_or(0, |x| x.len());
+        if size.is_some_and(|size| offset > size) {
+            offset = 0;
+        }
+        // 落とし終えてから名前を変える前に止まった
+        if offset > 0 && size == Some(offset) {
+            std::fs::rename(&part, path)?;
+            return Ok(Fetched::Resumed)
+        }
+
+        let mut request = self.request(Method::GET, &url, "*/*");
+        if offset > 0 {
+            request = request.header("Range", format!("bytes={offset}-"));
+        }
+        let mut res = request.send().await?;
+        // 範囲に対応していなければ最初から受け取り直す
+        let resumed = offset > 0 && res.status() == StatusCode::PARTIAL_CONTENT;
+        if !resumed && res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // `Content-Range: bytes */全体の大きさ`が`.part`と同じなら、もう全部ある
+            if complete_length(&res) == Some(offset) {
+                std::fs::rename(&part, path)?;
+                return Ok(Fetched::Resumed)
+            }
+            res = self.request(Method::GET, &url, "*/*").send().await?;
+        }
+        let mut res = res.error_for_status()?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(&part)?;
+        while let Some(chunk) = res.chunk().await? {
+            out.write_all(&chunk)?;
+        }
+        out.flush()?;
+        drop(out);
+        std::fs::rename(&part, path)?;
+
+        Ok(if resumed { Fetched::Resumed } else { Fetched::Downloaded })
+    }
+}
+
+/// 416の`Content-Range: bytes */<大きさ>`から全体の大きさを読む
+fn complete_length(res: &reqwest::Response) -> Option<u64> {
+    res.headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str().ok()?
+        .strip_prefix("bytes */")?
+        .trim()
+        .parse().ok()
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// 保存し終えたファイルを`--extract`なら展開する
+fn after_download(path: &Path, layout: &LayoutArgs) -> Result<(), ExecutionError> {
+    if !layout.extract {
+        return Ok(())
+    }
+
+    if let Some(target) = library_layout::extract(path)? {
+        println!("{}", message!("library.extracted", path = path.display(), target = target.display()));
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn list(client: &LibraryClient, json: bool) -> Result<(), ExecutionError> {
+    let items = client.items().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(())
+    }
+
+    for item in &items {
+        let files = item.downloadables.len();
+        if item.gift {
+            println!("{}", message!("library.row-gift", id = item.item_id, name = item.name, shop = item.shop, files = files));
+        } else {
+            println!("{}", message!("library.row", id = item.item_id, name = item.name, shop = item.shop, files = files));
+        }
+        for file in &item.downloadables {
+            println!("{}", message!("library.file", id = file.id, name = file.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// 一つのファイルを保存し、結果を表示する
+async fn fetch(client: &LibraryClient, item: &LibraryItem, file: &LibraryFile, path: &Path, layout: &LayoutArgs, shared: &SharedDirs) -> Result<Fetched, ExecutionError> {
+    let path_text = path.display();
+    let size = client.size(file).await?;
+    let fetched = client.download(file, path, size).await?;
+    match fetched {
+        Fetched::Skipped => println!("{}", message!("library.skipped", path = path_text)),
+        Fetched::Downloaded => println!("{}", message!("library.downloaded", path = path_text)),
+        Fetched::Resumed => println!("{}", message!("library.resumed", path = path_text)),
+    }
+    if fetched != Fetched::Skipped {
+        after_download(path, layout)?;
+    }
+    library_layout::write_sidecar(path, item, shared)?;
+
+    Ok(fetched)
+}
+
+/// 失敗したファイルがあっても残りを続け、最後に失敗した数を返す。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn download(client: &LibraryClient, dir: &Path, layout: &LayoutArgs) -> Result<(), ExecutionError> {
+    let items = client.items().await?;
+
+    let files = items.iter()
+        .flat_map(|item| item.downloadables.iter().map(move |file| (item, file, layout.layout.path(dir, item, file))))
+        .collect::<Vec<_>>();
+    if let Some(path) = library_layout::collision(files.iter().map(|(_, _, path)| path.as_path())) {
+        return Err(ExecutionError::LayoutCollision(path.to_path_buf()))
+    }
+    let shared = SharedDirs::new(files.iter().map(|(item, _, path)| (path.as_path(), item.item_id)));
+
+    let (mut downloaded, mut skipped, mut failed) = (0, 0, 0);
+    for (item, file, path) in &files {
+        match fetch(client, item, file, path, layout, &shared).await {
+            Ok(Fetched::Skipped) => skipped += 1,
+            Ok(Fetched::Downloaded | Fetched::Resumed) => downloaded += 1,
+            Err(error) => {
+                failed += 1;
+                eprintln!("{}", message!("library.failed", path = path.display(), error = error));
+            }
+        }
+    }
+
+    println!("{}", message!("library.download-summary", downloaded = downloaded, skipped = skipped));
+
+    if failed > 0 {
+        return Err(ExecutionError::DownloadFailed { count: failed })
+    }
+
+    Ok(())
+}
+
+/// 前回の`library sync`からの変化
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Change {
+    New,
+    /// 同じIDのまま名前か大きさが変わった
+    Updated,
+}
+
+/// ライブラリに今あるダウンロードファイル
+struct RemoteFile<'a> {
+    item: &'a LibraryItem,
+    file: &'a LibraryFile,
+    size: Option<u64>,
+}
+
+/// 変わったファイルを、ライブラリに並んでいる順に返す。消えたファイルは最後に置く
+fn plan<'a>(indexed: &[IndexedFile], remote: &'a [RemoteFile<'a>]) -> Vec<(&'a RemoteFile<'a>, Change)> {
+    remote.iter()
+        .filter_map(|x| {
+            let Some(previous) = indexed.iter().find(|y| y.id == x.file.id) else {
+                return Some((x, Change::New))
+            };
+            // 大きさが分からなければ名前だけで比べる
+            let resized = x.size.is_some() && previous.size.is_some() && x.size != previous.size;
+            (previous.name != x.file.name || resized).then_some((x, Change::Updated))
+        })
+        .collect()
+}
+
+/// 同じ大きさのファイルがすでにある。大きさが分からなければ比べられないので`false`
+fn already_saved(path: &Path, size: Option<u64>) -> bool {
+    size.is_some_and(|size| std::fs::metadata(path).is_ok_and(|x| x.is_file() && x.len() == size))
+}
+
+/// ライブラリから消えたファイル。保存したファイルは残す
+fn removed<'a>(indexed: &'a [IndexedFile], remote: &[RemoteFile]) -> Vec<&'a IndexedFile> {
+    indexed.iter().filter(|x| remote.iter().all(|y| y.file.id != x.id)).collect()
+}
+
+/// 保存したファイルを展開して索引に載せる
+fn settle(index: &LibraryIndex, remote: &RemoteFile, path: &Path, layout: &LayoutArgs, shared: &SharedDirs, fetched: Fetched) -> Result<(), ExecutionError> {
+    // 保存先にもう同じものがあって落とさなかったなら、展開も済んでいる
+    if fetched != Fetched::Skipped {
+        after_download(path, layout)?;
+    }
+    index.record_file(remote.item.item_id, remote.file, remote.size, path)?;
+    library_layout::write_sidecar(path, remote.item, shared)?;
+
+    Ok(())
+}
+
+/// 前回から増えたか変わったファイルだけをダウンロードし、アイテムごとの変更点を表示する。
+/// 失敗したファイルがあっても残りを続け、最後に失敗した数を返す。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn sync(client: &LibraryClient, dir: &Path, index: Option<&Path>, layout: &LayoutArgs) -> Result<(), ExecutionError> {
+    let index = index.map_or_else(|| dir.join(INDEX_FILE_NAME), Path::to_path_buf);
+    if let Some(parent) = index.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let index = LibraryIndex::open(&index)?;
+    let items = client.items().await?;
+
+    let mut remote = vec![];
+    for item in &items {
+        index.record_item(item)?;
+        for file in &item.downloadables {
+            remote.push(RemoteFile { item, file, size: client.size(file).await? });
+        }
+    }
+
+    let paths = remote.iter().map(|x| layout.layout.path(dir, x.item, x.file)).collect::<Vec<_>>();
+    if let Some(path) = library_layout::collision(paths.iter().map(PathBuf::as_path)) {
+        return Err(ExecutionError::LayoutCollision(path.to_path_buf()))
+    }
+    let shared = SharedDirs::new(paths.iter().zip(&remote).map(|(path, x)| (path.as_path(), x.item.item_id)));
+
+    let indexed = index.files()?;
+    // `library download`で保存済みのファイルは、新しいファイルとして落とし直さずに覚えるだけにする
+    let (saved, changes): (Vec<_>, Vec<_>) = plan(&indexed, &remote).into_iter()
+        .partition(|(remote, change)| *change == Change::New && already_saved(&layout.layout.path(dir, remote.item, remote.file), remote.size));
+    for (remote, _) in saved {
+        index.record_file(remote.item.item_id, remote.file, remote.size, &layout.layout.path(dir, remote.item, remote.file))?;
+    }
+    let removed = removed(&indexed, &remote);
+    if changes.is_empty() && removed.is_empty() {
+        println!("{}", message!("library.up-to-date"));
+        return Ok(())
+    }
+
+    let mut last_item = None;
+    let mut failed = 0;
+    for (remote, change) in changes {
+        let path = layout.layout.path(dir, remote.item, remote.file);
+        if change == Change::Updated {
+            // 前の版の途中までのファイルから再開しない
+            let _ = std::fs::remove_file(partial_path(&path));
+        }
+        // 失敗したファイルは索引に載せないので、次の`library sync`でもう一度落とす
+        let fetched = client.download(remote.file, &path, remote.size).await;
+        if let Err(error) = fetched.and_then(|fetched| settle(&index, remote, &path, layout, &shared, fetched)) {
+            failed += 1;
+            eprintln!("{}", message!("library.failed", path = path.display(), error = error));
+            continue
+        }
+
+        if last_item != Some(remote.item.item_id) {
+            last_item = Some(remote.item.item_id);
+            println!("{}", message!("library.changelog-item", id = remote.item.item_id, name = remote.item.name, shop = remote.item.shop));
+        }
+        if change == Change::New {
+            println!("{}", message!("library.change-new", name = remote.file.name, path = path.display()));
+        } else {
+            println!("{}", message!("library.change-updated", name = remote.file.name, path = path.display()));
+        }
+    }
+
+    for file in removed {
+        index.remove_file(file.id)?;
+        println!("{}", message!("library.change-removed", id = file.item_id, name = file.name));
+    }
+
+    if failed > 0 {
+        return Err(ExecutionError::DownloadFailed { count: failed })
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use crate::booth::{ItemId, LibraryFile, LibraryItem};
+    use crate::library::{partial_path, plan, removed, Change, RemoteFile};
+    use crate::library_index::IndexedFile;
+
+    #[test]
+    fn partial() {
+        assert_eq!(partial_path(Path::new("booth/42/v1.zip")), Path::new("booth/42/v1.zip.part"));
+    }
+
+    #[test]
+    fn detect_changes() {
+        let item_id = ItemId::new(42).unwrap();
+        let item = LibraryItem { item_id, name: String::new(), shop: String::new(), purchased_at: None, gift: false, downloadables: vec![] };
+        let files = [101, 102, 103, 104].map(|id| LibraryFile { id, name: format!("{id}.zip"), url: String::new() });
+        let indexed = |id: u64, name: &str, size: Option<u64>| IndexedFile { id, item_id, name: name.to_string(), size };
+
+        let remote = [
+            RemoteFile { item: &item, file: &files[0], size: Some(10) },
+            // 大きさが変わった
+            RemoteFile { item: &item, file: &files[1], size: Some(20) },
+            // 名前が変わった
+            RemoteFile { item: &item, file: &files[2], size: None },
+            RemoteFile { item: &item, file: &files[3], size: Some(40) },
+        ];
+        let indexed = [
+            indexed(101, "101.zip", Some(10)),
+            indexed(102, "102.zip", Some(19)),
+            indexed(103, "old.zip", Some(30)),
+            indexed(105, "105.zip", Some(50)),
+        ];
+
+        let changes = plan(&indexed, &remote).into_iter().map(|(x, change)| (x.file.id, change)).collect::<Vec<_>>();
+        assert_eq!(changes, [(102, Change::Updated), (103, Change::Updated), (104, Change::New)]);
+        assert_eq!(removed(&indexed, &remote).iter().map(|x| x.id).collect::<Vec<_>>(), [105]);
+    }
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+use sqlite3::{Connection, State};
+use crate::booth::{ItemId, LibraryFile, LibraryItem};
+use crate::sqlite::{from_sql, read_optional, to_sql, SQLite3ErrorWithCompare};
+
+/// `library sync`が前回までに保存したダウンロードファイルを覚えておくデータベース
+pub struct LibraryIndex {
+    connection: Connection,
+}
+
+/// 保存済みのダウンロードファイル
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct IndexedFile {
+    pub id: u64,
+    pub item_id: ItemId,
+    pub name: String,
+    pub size: Option<u64>,
+}
+
+const SCHEMA: &str = r"
+create table if not exists items (
+    item_id integer primary key,
+    name text not null,
+    shop text not null,
+    gift integer not null,
+    purchased_at text
+);
+create table if not exists downloadables (
+    id integer primary key,
+    item_id integer not null,
+    name text not null,
+    size integer,
+    path text not null,
+    downloaded_at text not null
+);
+";
+
+impl LibraryIndex {
+    /// なければ作る
+    pub fn open(path: &Path) -> Result<Self, SQLite3ErrorWithCompare> {
+        let connection = sqlite3::open(path)?;
+        connection.execute(SCHEMA)?;
+
+        Ok(Self { connection })
+    }
+
+    pub fn files(&self) -> Result<Vec<IndexedFile>, SQLite3ErrorWithCompare> {
+        let mut statement = self.connection.prepare("select id, item_id, name, size from downloadables order by id")?;
+        let mut files = vec![];
+        while statement.next()? == State::Row {
+            let item_id = statement.read::<i64>(1)?;
+            let item_id = from_sql(item_id).ok().and_then(ItemId::new).ok_or_else(|| SQLite3ErrorWithCompare::out_of_range(item_id))?;
+            files.push(IndexedFile {
+                id: from_sql(statement.read::<i64>(0)?)?,
+                item_id,
+                name: statement.read::<String>(2)?,
+                size: read_optional(&statement, 3)?,
+            });
+        }
+
+        Ok(files)
+    }
+
+    pub fn record_item(&self, item: &LibraryItem) -> Result<(), SQLite3ErrorWithCompare> {
+        let mut statement = self.connection.prepare("insert or replace into items (item_id, name, shop, gift, purchased_at) values (?, ?, ?, ?, ?)")?;
+        statement.bind(1, i64::from(item.item_id.get()))?;
+        statement.bind(2, item.name.as_str())?;
+        statement.bind(3, item.shop.as_str())?;
+        statement.bind(4, i64::from(item.gift))?;
+        let purchased_at = item.purchased_at.map(|x| x.to_rfc3339());
+        match &purchased_at {
+            Some(at) => statement.bind(5, at.as_str())?,
+            None => statement.bind(5, ())?,
+        }
+        while statement.next()? == State::Row {}
+
+        Ok(())
+    }
+
+    /// 保存し終えたファイルを覚える
+    pub fn record_file(&self, item_id: ItemId, file: &LibraryFile, size: Option<u64>, path: &Path) -> Result<(), SQLite3ErrorWithCompare> {
+        let mut statement = self.connection.prepare("insert or replace into downloadables (id, item_id, name, size, path, downloaded_at) values (?, ?, ?, ?, ?, ?)")?;
+        statement.bind(1, to_sql(file.id)?)?;
+        statement.bind(2, i64::from(item_id.get()))?;
+        statement.bind(3, file.name.as_str())?;
+        match size {
+            Some(size) => statement.bind(4, to_sql(size)?)?,
+            None => statement.bind(4, ())?,
+        }
+        statement.bind(5, path.to_string_lossy().as_ref())?;
+        statement.bind(6, chrono::Utc::now().to_rfc3339().as_str())?;
+        while statement.next()? == State::Row {}
+
+        Ok(())
+    }
+
+    pub fn remove_file(&self, id: u64) -> Result<(), SQLite3ErrorWithCompare> {
+        let mut statement = self.connection.prepare("delete from downloadables where id = ?")?;
+        statement.bind(1, to_sql(id)?)?;
+        while statement.next()? == State::Row {}
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use crate::booth::{ItemId, LibraryFile, LibraryItem};
+    use crate::library_index::{IndexedFile, LibraryIndex};
+
+    #[test]
+    fn round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = LibraryIndex::open(&dir.path().join("library.sqlite3")).unwrap();
+        let item_id = ItemId::new(42).unwrap();
+        let item = LibraryItem { item_id, name: "Sample".to_string(), shop: "Shop".to_string(), purchased_at: None, gift: true, downloadables: vec![] };
+        let file = |id: u64, name: &str| LibraryFile { id, name: name.to_string(), url: String::new() };
+
+        index.record_item(&item).unwrap();
+        index.record_file(item_id, &file(101, "v1.zip"), Some(5000), Path::new("42/v1.zip")).unwrap();
+        index.record_file(item_id, &file(102, "manual.pdf"), None, Path::new("42/manual.pdf")).unwrap();
+        index.record_file(item_id, &file(101, "v1.zip"), Some(6000), Path::new("42/v1.zip")).unwrap();
+        index.remove_file(102).unwrap();
+        assert!(index.record_file(item_id, &file(u64::MAX, "huge.zip"), None, Path::new("42/huge.zip")).is_err());
+
+        assert_eq!(index.files().unwrap(), [IndexedFile { id: 101, item_id, name: "v1.zip".to_string(), size: Some(6000) }]);
+    }
+}
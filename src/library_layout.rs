@@ -0,0 +1,285 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use crate::booth::{ItemId, LibraryFile, LibraryItem};
+use crate::ExecutionError;
+use crate::message::message;
+
+/// 多くのファイルシステムは255バイトまで。拡張子を残して切り詰める分の余裕を持たせる
+const MAX_NAME_BYTES: usize = 200;
+const PLACEHOLDERS: &[&str] = &["shop", "item_name", "item_id", "file_name", "file_id"];
+
+/// `{shop}/{item_name} ({item_id})/{file_name}`のような、`--dir`からの保存先
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Layout {
+    segments: Vec<String>,
+}
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ParseLayoutError {
+    #[error("{}", message!("error.layout.unknown-placeholder", placeholder = .0))]
+    UnknownPlaceholder(String),
+    #[error("{}", message!("error.layout.unclosed-brace"))]
+    UnclosedBrace,
+    #[error("{}", message!("error.layout.empty-segment"))]
+    EmptySegment,
+    #[error("{}", message!("error.layout.no-file-name"))]
+    NoFileName,
+}
+
+impl FromStr for Layout {
+    type Err = ParseLayoutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Windowsで書いた区切りも受け付ける
+        let segments = s.split(['/', '\\']).map(ToString::to_string).collect::<Vec<_>>();
+        for segment in &segments {
+            // 保存先が`--dir`の外に出ないように、`..`も許さない
+            if segment.trim().is_empty() || segment == "." || segment == ".." {
+                return Err(ParseLayoutError::EmptySegment)
+            }
+            for placeholder in placeholders(segment)? {
+                if !PLACEHOLDERS.contains(&placeholder) {
+                    return Err(ParseLayoutError::UnknownPlaceholder(placeholder.to_string()))
+                }
+            }
+        }
+
+        let last = placeholders(segments.last().expect("split yields at least one"))?;
+        if !last.contains(&"file_name") && !last.contains(&"file_id") {
+            return Err(ParseLayoutError::NoFileName)
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+fn placeholders(segment: &str) -> Result<Vec<&str>, ParseLayoutError> {
+    let mut found = vec![];
+    let mut rest = segment;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err(ParseLayoutError::UnclosedBrace)
+        };
+        found.push(&rest[start + 1..start + end]);
+        rest = &rest[start + end + 1..];
+    }
+
+    Ok(found)
+}
+
+impl Layout {
+    /// 値はそれぞれの部分ごとに`sanitize`するので、名前に`/`を含んでいてもディレクトリは増えない
+    pub fn path(&self, dir: &Path, item: &LibraryItem, file: &LibraryFile) -> PathBuf {
+        let file_name = if file.name.trim().is_empty() { file.id.to_string() } else { file.name.clone() };
+        let values = [
+            ("shop", item.shop.clone()),
+            ("item_name", item.name.clone()),
+            ("item_id", item.item_id.to_string()),
+            ("file_name", file_name),
+            ("file_id", file.id.to_string()),
+        ];
+
+        let mut path = dir.to_path_buf();
+        for segment in &self.segments {
+            let rendered = values.iter().fold(segment.clone(), |x, (placeholder, value)| x.replace(&format!("{{{placeholder}}}"), value));
+            path.push(sanitize(&rendered));
+        }
+
+        path
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        "{item_id}/{file_name}".parse().expect("valid layout")
+    }
+}
+
+/// どのOSでも使えるファイル名にする
+pub fn sanitize(name: &str) -> String {
+    // Windowsの予約名
+    const RESERVED: &[&str] = &[
+        "CON", "PRN", "AUX", "NUL",
+        "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+        "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+
+    let replaced = name.chars()
+        .map(|c| if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { '_' } else { c })
+        .collect::<String>();
+    // Windowsは末尾の空白とピリオドを黙って取り除く
+    let mut name = replaced.trim().trim_end_matches(['.', ' ']).to_string();
+
+    if name.len() > MAX_NAME_BYTES {
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() && extension.len() <= 16 => (stem.to_string(), format!(".{extension}")),
+            _ => (name.clone(), String::new()),
+        };
+        let mut end = MAX_NAME_BYTES - extension.len();
+        while !stem.is_char_boundary(end) {
+            end -= 1;
+        }
+        name = format!("{stem}{extension}", stem = stem[..end].trim_end());
+    }
+
+    if name.is_empty() {
+        return "_".to_string()
+    }
+
+    // `CON.txt`も使えない
+    let stem = name.split('.').next().unwrap_or_default();
+    if RESERVED.iter().any(|x| x.eq_ignore_ascii_case(stem)) {
+        name.insert(0, '_');
+    }
+
+    name
+}
+
+/// 複数のファイルが同じ保存先になれば、その一つ。大文字と小文字を区別しないファイルシステムに合わせる
+pub fn collision<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Option<&'a Path> {
+    let mut seen = HashSet::new();
+    paths.into_iter().find(|path| !seen.insert(path.to_string_lossy().to_lowercase()))
+}
+
+/// 複数のアイテムのファイルが入るディレクトリ。`collision`と同じく大文字と小文字を区別しない
+pub struct SharedDirs(HashSet<String>);
+
+impl SharedDirs {
+    pub fn new<'a>(files: impl IntoIterator<Item = (&'a Path, ItemId)>) -> Self {
+        let mut items = HashMap::<_, HashSet<_>>::new();
+        for (path, item_id) in files {
+            let dir = path.parent().unwrap_or(path).to_string_lossy().to_lowercase();
+            items.entry(dir).or_default().insert(item_id);
+        }
+
+        Self(items.into_iter().filter(|(_, x)| x.len() > 1).map(|(dir, _)| dir).collect())
+    }
+
+    fn contains(&self, dir: &Path) -> bool {
+        self.0.contains(&dir.to_string_lossy().to_lowercase())
+    }
+}
+
+/// アイテムの情報を書き出す、ダウンロードしたファイルの隣に置くファイル。
+/// 複数のアイテムが同じディレクトリに入るレイアウトでは、上書きし合わないようにアイテムごとに分ける
+fn sidecar_name(dir: &Path, item: &LibraryItem, shared: &SharedDirs) -> String {
+    if shared.contains(dir) {
+        format!("booth-{id}.json", id = item.item_id)
+    } else {
+        "booth.json".to_string()
+    }
+}
+
+/// ダウンロードしたファイルと同じディレクトリに、アイテムの情報と購入日を書き出す
+pub fn write_sidecar(file_path: &Path, item: &LibraryItem, shared: &SharedDirs) -> Result<(), ExecutionError> {
+    let Some(dir) = file_path.parent() else {
+        return Ok(())
+    };
+
+    std::fs::create_dir_all(dir)?;
+    serde_json::to_writer_pretty(File::create(dir.join(sidecar_name(dir, item, shared)))?, item)?;
+
+    Ok(())
+}
+
+/// `v1.zip`を隣の`v1/`に展開する。zipでなければ何もせず`None`
+pub fn extract(path: &Path) -> Result<Option<PathBuf>, ExecutionError> {
+    if !path.extension().is_some_and(|x| x.eq_ignore_ascii_case("zip")) {
+        return Ok(None)
+    }
+
+    let target = path.with_extension("");
+    // 展開先の外を指す名前はzipクレートが弾く
+    zip::ZipArchive::new(File::open(path)?)?.extract(&target)?;
+
+    Ok(Some(target))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+    use std::path::Path;
+    use crate::booth::{ItemId, LibraryFile, LibraryItem};
+    use crate::library_layout::{collision, extract, sanitize, write_sidecar, Layout, ParseLayoutError, SharedDirs};
+
+    #[test]
+    fn parse_layout() {
+        assert!("{shop}/{item_name} ({item_id})/{file_name}".parse::<Layout>().is_ok());
+        assert_eq!("{shop}/{price}/{file_name}".parse::<Layout>(), Err(ParseLayoutError::UnknownPlaceholder("price".to_string())));
+        assert_eq!("{shop/{file_name}".parse::<Layout>(), Err(ParseLayoutError::UnclosedBrace));
+        assert_eq!("{shop}/../{file_name}".parse::<Layout>(), Err(ParseLayoutError::EmptySegment));
+        assert_eq!("{shop}//{file_name}".parse::<Layout>(), Err(ParseLayoutError::EmptySegment));
+        assert_eq!("{file_name}/{shop}".parse::<Layout>(), Err(ParseLayoutError::NoFileName));
+    }
+
+    #[test]
+    fn render_layout() {
+        let item = LibraryItem { item_id: ItemId::new(42).unwrap(), name: "Avatar: v2/Pro?".to_string(), shop: "Shop.".to_string(), purchased_at: None, gift: false, downloadables: vec![] };
+        let file = LibraryFile { id: 101, name: "v1.zip".to_string(), url: String::new() };
+        let layout = "{shop}/{item_name} ({item_id})/{file_name}".parse::<Layout>().unwrap();
+
+        assert_eq!(layout.path(Path::new("booth"), &item, &file), Path::new("booth/Shop/Avatar_ v2_Pro_ (42)/v1.zip"));
+        assert_eq!(Layout::default().path(Path::new("booth"), &item, &file), Path::new("booth/42/v1.zip"));
+
+        // サーバーから来た名前で`--dir`の外に出ない
+        let file = LibraryFile { id: 101, name: "../../etc/passwd".to_string(), url: String::new() };
+        assert_eq!(Layout::default().path(Path::new("booth"), &item, &file), Path::new("booth/42/.._.._etc_passwd"));
+    }
+
+    #[test]
+    fn detect_collision() {
+        let item = |id: u32| LibraryItem { item_id: ItemId::new(id).unwrap(), name: "Avatar".to_string(), shop: "Shop".to_string(), purchased_at: None, gift: false, downloadables: vec![] };
+        let file = LibraryFile { id: 101, name: "README.txt".to_string(), url: String::new() };
+        let other = LibraryFile { id: 102, name: "readme.txt".to_string(), ..file.clone() };
+        let layout = "{shop}/{file_name}".parse::<Layout>().unwrap();
+        let paths = [layout.path(Path::new("booth"), &item(1), &file), layout.path(Path::new("booth"), &item(2), &other)];
+        assert_eq!(collision(paths.iter().map(AsRef::as_ref)), Some(Path::new("booth/Shop/readme.txt")));
+
+        let paths = [Layout::default().path(Path::new("booth"), &item(1), &file), Layout::default().path(Path::new("booth"), &item(2), &file)];
+        assert_eq!(collision(paths.iter().map(AsRef::as_ref)), None);
+
+        // 同じディレクトリに入れば、アイテムの情報は別々のファイルに書く
+        let dir = tempfile::tempdir().unwrap();
+        let files = [(dir.path().join("shared/README.txt"), 1), (dir.path().join("shared/readme.txt"), 2), (dir.path().join("own/README.txt"), 1)];
+        let shared = SharedDirs::new(files.iter().map(|(path, id)| (path.as_path(), ItemId::new(*id).unwrap())));
+        for (path, id) in &files {
+            write_sidecar(path, &item(*id), &shared).unwrap();
+        }
+        assert!(dir.path().join("shared/booth-1.json").is_file());
+        assert!(dir.path().join("shared/booth-2.json").is_file());
+        assert!(!dir.path().join("shared/booth.json").exists());
+        assert!(dir.path().join("own/booth.json").is_file());
+    }
+
+    #[test]
+    fn sanitize_names() {
+        assert_eq!(sanitize("a<b>c:d\"e|f?g*h\\i/j"), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(sanitize("tab\there"), "tab_here");
+        assert_eq!(sanitize(" name. . "), "name");
+        assert_eq!(sanitize(".."), "_");
+        assert_eq!(sanitize(""), "_");
+        assert_eq!(sanitize("con.txt"), "_con.txt");
+        assert_eq!(sanitize("console.txt"), "console.txt");
+
+        let long = sanitize(&format!("{}.zip", "あ".repeat(100)));
+        assert!(long.len() <= 200);
+        assert!(long.ends_with("あ.zip"));
+    }
+
+    #[test]
+    fn extract_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("v1.zip");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+        writer.start_file("inner/readme.txt", zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(b"hello").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(extract(&archive).unwrap(), Some(dir.path().join("v1")));
+        assert_eq!(std::fs::read_to_string(dir.path().join("v1/inner/readme.txt")).unwrap(), "hello");
+        assert_eq!(extract(&dir.path().join("manual.pdf")).unwrap(), None);
+    }
+}
@@ -0,0 +1,132 @@
+use std::str::FromStr;
+use std::sync::OnceLock;
+use crate::message::message;
+
+/// BOOTHが対応している言語。このツール自身のメッセージは日本語か英語で表示し、それ以外は英語で代用する。
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Language {
+    Japanese,
+    English,
+    Korean,
+    SimplifiedChinese,
+    TraditionalChinese,
+}
+
+static CURRENT: OnceLock<Language> = OnceLock::new();
+
+impl Language {
+    /// `Accept-Language`に送る値
+    pub const fn accept_language(self) -> &'static str {
+        match self {
+            Self::Japanese => "ja",
+            Self::English => "en",
+            Self::Korean => "ko",
+            Self::SimplifiedChinese => "zh-cn",
+            Self::TraditionalChinese => "zh-tw",
+        }
+    }
+
+    /// POSIXのロケール名 (`ja_JP.UTF-8`)、BCP 47の言語タグ (`zh-Hant-TW`)のどちらも受け付ける。
+    fn from_tag(tag: &str) -> Option<Self> {
+        // エンコーディングと修飾子は関係ない
+        let tag = tag.split(['.', '@']).next().unwrap_or_default().to_ascii_lowercase().replace('_', "-");
+        let mut subtags = tag.split('-');
+        let language = match subtags.next()? {
+            "ja" => Self::Japanese,
+            "en" | "c" | "posix" => Self::English,
+            "ko" => Self::Korean,
+            "zh" => {
+                // 簡体字か繁体字かは文字体系か地域で決まる。どちらもなければ簡体字とみなす。
+                let traditional = subtags.any(|x| matches!(x, "hant" | "tw" | "hk" | "mo"));
+                if traditional {
+                    Self::TraditionalChinese
+                } else {
+                    Self::SimplifiedChinese
+                }
+            }
+            _ => return None,
+        };
+
+        Some(language)
+    }
+
+    /// `BOOTH_LANGUAGE`、`LC_ALL`、`LC_MESSAGES`、`LANG`の順に見る。
+    /// POSIXと同じく空でない最初の変数だけで決め、対応していない言語なら英語にする。
+    fn from_env() -> Self {
+        let from_variables = ["BOOTH_LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+
+        let Some(value) = from_variables else {
+            return user_default_locale()
+                .and_then(|locale| Self::from_tag(&locale))
+                .unwrap_or(Self::English)
+        };
+
+        Self::from_tag(&value).unwrap_or(Self::English)
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_tag(s).ok_or_else(|| message!("error.unsupported-language", language = s))
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(windows)] {
+        /// Windowsでは環境変数ではなくユーザーの設定から取る
+        fn user_default_locale() -> Option<String> {
+            // LOCALE_NAME_MAX_LENGTH
+            const MAX_LENGTH: usize = 85;
+
+            #[link(name = "kernel32")]
+            extern "system" {
+                fn GetUserDefaultLocaleName(locale_name: *mut u16, locale_name_length: i32) -> i32;
+            }
+
+            let mut buffer = [0u16; MAX_LENGTH];
+            // SAFETY: the buffer is valid for MAX_LENGTH elements, and the API writes at most that.
+            let written = unsafe { GetUserDefaultLocaleName(buffer.as_mut_ptr(), i32::try_from(MAX_LENGTH).expect("small enough")) };
+            // 終端のNULを含む長さが返ってくる。失敗した場合は0。
+            let written = usize::try_from(written).ok().filter(|x| *x > 1)?;
+            Some(String::from_utf16_lossy(&buffer[..written - 1]))
+        }
+    } else {
+        const fn user_default_locale() -> Option<String> {
+            None
+        }
+    }
+}
+
+/// `--language`が指定されていればそれを使い、なければ環境から決める。最初の一回だけ有効。
+pub fn init(explicit: Option<Language>) {
+    CURRENT.get_or_init(|| explicit.unwrap_or_else(Language::from_env));
+}
+
+pub fn current() -> Language {
+    *CURRENT.get_or_init(Language::from_env)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::locale::Language;
+
+    #[test]
+    fn from_tag() {
+        assert_eq!(Language::from_tag("ja_JP.UTF-8"), Some(Language::Japanese));
+        assert_eq!(Language::from_tag("ja-JP"), Some(Language::Japanese));
+        assert_eq!(Language::from_tag("en_US@euro"), Some(Language::English));
+        assert_eq!(Language::from_tag("C"), Some(Language::English));
+        assert_eq!(Language::from_tag("ko-KR"), Some(Language::Korean));
+        assert_eq!(Language::from_tag("zh-cn"), Some(Language::SimplifiedChinese));
+        assert_eq!(Language::from_tag("zh-Hans-CN"), Some(Language::SimplifiedChinese));
+        assert_eq!(Language::from_tag("zh_TW.UTF-8"), Some(Language::TraditionalChinese));
+        assert_eq!(Language::from_tag("zh-Hant"), Some(Language::TraditionalChinese));
+        assert_eq!(Language::from_tag("fr_FR"), None);
+        assert_eq!(Language::from_tag(""), None);
+    }
+}
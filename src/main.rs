@@ -4,80 +4,620 @@
 mod pretty_size;
 mod booth;
 mod sqlite;
+mod manage;
+mod sync;
+mod prompt;
+mod quota;
+mod locale;
+mod message;
+mod form;
+mod item;
+mod items;
+mod variation;
+mod gallery;
+mod public;
+mod verify;
+mod orders;
+mod sales;
+mod library;
+mod library_index;
+mod library_layout;
+mod watch;
+mod messages;
+mod digest;
+mod stock;
+mod sale;
+#[cfg(test)]
+mod fixture;
 
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
-use clap::Parser;
-use reqwest::multipart::{Form, Part};
-use select::predicate::Predicate;
+use std::process::ExitCode;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use clap::{CommandFactory, FromArgMatches};
 use strum::EnumString;
 use thiserror::Error;
-use crate::booth::{UploadError, UploadResult};
+use crate::booth::{ItemDraft, ItemFormError, ItemId, ItemRef, PublishState, UploadError, UploadResult};
+use crate::form::SubmitError;
+use crate::gallery::GalleryError;
+use crate::stock::StockError;
+use crate::sale::{SaleError, SaleTarget};
+use crate::library::LibraryClient;
+use crate::library_layout::Layout;
+use crate::item::{ItemChanges, ItemTemplate};
+use crate::orders::ExportFormat;
+use crate::sales::{GroupBy, ReportFormat};
+use crate::locale::Language;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::pretty_size::{pretty_size, ByteSize};
 use crate::sqlite::SQLite3ErrorWithCompare;
 
-/// Utility around booth.pm, developed by Kisaragi Marine.
-/// This project is not related, developed, nor affiliated by pixiv inc.
-/// Please refer to <https://policies.pixiv.net/> and <https://policies.pixiv.net/#booth>
-/// before use.
-#[derive(Parser)]
-#[command(author, version, about, long_about)]
+// ヘルプはメッセージカタログの`help.*`にだけ書く。引数を足したら両方の言語に足すこと
+#[derive(clap::Parser)]
+#[command(author, version)]
+struct CommandLine {
+    #[clap(long, global = true)]
+    language: Option<Language>,
+    #[clap(subcommand)]
+    subcommand: CommandLineSubCommand,
+}
+
+#[derive(clap::Subcommand)]
 enum CommandLineSubCommand {
     GetAuthorizationToken {
         #[clap(short, long)]
-        /// Path to `cookies.sqlite` if firefox, `Cookies` if chromium.
         cookie_file: PathBuf,
         #[clap(short, long)]
-        /// accepts `firefox` or `chromium`.
-        /// Internet Explorer, Safari, Sleipnir, Lunaspace, legacy Edge and legacy Opera are unsupported.
         browser: Browser,
     },
     Upload {
-        #[clap(short = 'i', long)]
-        /// Your item's id. e.g. https://booth.pm/ja/items/3519955 -> 3519955
-        booth_item_id: i32,
+        #[clap(flatten)]
+        item: ItemArgs,
         #[clap(short = 'p', long)]
-        /// Your local path to be uploaded.
         artifact_path: PathBuf,
-        #[clap(short = 't', long, long = "token")]
-        /// Can be grabbed by `get-authorization-token` subcommand.
-        login_token: String,
         #[clap(long)]
-        /// Sets `Accept-Language` in HTTP request, sending its value from your environment
-        /// variable to localize error to your language.
-        ///
-        /// This flag does not have effect on non-*nix platform.
-        localize_remote_error: bool,
+        variation: Option<String>,
+        #[clap(flatten)]
+        quota_guard: QuotaGuardArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Sync {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(short = 'd', long)]
+        dir: PathBuf,
+        #[clap(long)]
+        state_file: Option<PathBuf>,
+        #[clap(long)]
+        prune: bool,
+        #[clap(short = 'y', long)]
+        yes: bool,
+        #[clap(long)]
+        dry_run: bool,
+        #[clap(flatten)]
+        quota_guard: QuotaGuardArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Quota {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Items {
+        #[clap(subcommand)]
+        command: ItemsCommand,
+    },
+    Item {
+        #[clap(subcommand)]
+        command: ItemCommand,
+    },
+    Variation {
+        #[clap(subcommand)]
+        command: VariationCommand,
+    },
+    Gallery {
+        #[clap(subcommand)]
+        command: GalleryCommand,
+    },
+    Verify {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(long = "expect-file")]
+        expect_files: Vec<String>,
+        #[clap(long)]
+        manifest: Option<PathBuf>,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Orders {
+        #[clap(subcommand)]
+        command: OrdersCommand,
+    },
+    Sales {
+        #[clap(subcommand)]
+        command: SalesCommand,
+    },
+    Sale {
+        #[clap(subcommand)]
+        command: SaleCommand,
+    },
+    Stock {
+        #[clap(subcommand)]
+        command: StockCommand,
+    },
+    Digest {
+        #[clap(long)]
+        state_file: Option<PathBuf>,
+        #[clap(long, default_value_t = 5)]
+        low_stock: u32,
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Messages {
+        #[clap(subcommand)]
+        command: MessagesCommand,
+    },
+    Library {
+        #[clap(subcommand)]
+        command: LibraryCommand,
+    },
+    Wishlist {
+        #[clap(subcommand)]
+        command: WishlistCommand,
+    },
+    Watch {
+        #[clap(subcommand)]
+        command: WatchCommand,
+    },
+    Public {
+        #[clap(subcommand)]
+        command: PublicCommand,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum OrdersCommand {
+    Export {
+        #[clap(long)]
+        from: NaiveDate,
+        #[clap(long)]
+        to: NaiveDate,
+        #[clap(long, default_value = "csv")]
+        format: ExportFormat,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum SalesCommand {
+    Report {
+        #[clap(long)]
+        orders: PathBuf,
+        #[clap(long)]
+        by: GroupBy,
+        #[clap(long)]
+        from: Option<NaiveDate>,
+        #[clap(long)]
+        to: Option<NaiveDate>,
+        #[clap(long, requires = "compare_to")]
+        compare_from: Option<NaiveDate>,
+        #[clap(long, requires = "compare_from")]
+        compare_to: Option<NaiveDate>,
+        #[clap(long, default_value = "table")]
+        format: ReportFormat,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum SaleCommand {
+    Start {
+        #[clap(long, value_parser = clap::value_parser!(u8).range(1..100))]
+        percent: u8,
+        #[clap(long, value_delimiter = ',', required = true)]
+        items: Vec<SaleTarget>,
+        #[clap(long)]
+        until: NaiveDate,
+        #[clap(long)]
+        ledger: Option<PathBuf>,
+        #[clap(short = 'y', long)]
+        yes: bool,
+        #[clap(long)]
+        dry_run: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    End {
+        #[clap(long)]
+        ledger: Option<PathBuf>,
+        #[clap(long)]
+        if_due: bool,
+        #[clap(long)]
+        skip_conflicts: bool,
+        #[clap(short = 'y', long)]
+        yes: bool,
+        #[clap(long)]
+        dry_run: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum StockCommand {
+    Get {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(long)]
+        variation: Option<String>,
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Set {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(long)]
+        variation: String,
+        stock: u32,
+        #[clap(long)]
+        decrement_on: Option<u32>,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum MessagesCommand {
+    List {
+        #[clap(long)]
+        unread: bool,
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Show {
+        thread: u64,
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Reply {
+        thread: u64,
+        #[clap(long)]
+        body_file: PathBuf,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum LibraryCommand {
+    List {
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Download {
+        #[clap(long)]
+        dir: PathBuf,
+        #[clap(flatten)]
+        layout: LayoutArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Sync {
+        #[clap(long)]
+        dir: PathBuf,
+        #[clap(long)]
+        index: Option<PathBuf>,
+        #[clap(flatten)]
+        layout: LayoutArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum WishlistCommand {
+    List {
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum WatchCommand {
+    Prices {
+        items: Vec<ItemRef>,
+        #[clap(long)]
+        items_file: Option<PathBuf>,
+        #[clap(long, default_value = "booth-watch.sqlite3")]
+        db: PathBuf,
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum PublicCommand {
+    Item {
+        item: ItemRef,
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ItemsCommand {
+    List {
+        #[clap(long)]
+        status: Option<PublishState>,
+        #[clap(long)]
+        name_match: Option<String>,
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ItemCommand {
+    Get {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(long)]
+        json: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Set {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(flatten)]
+        changes: ItemChanges,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Export {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Import {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(short = 'f', long)]
+        file: PathBuf,
+        #[clap(short = 'y', long)]
+        yes: bool,
+        #[clap(long)]
+        dry_run: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Create {
+        #[clap(long, required_unless_present = "clone", conflicts_with = "clone")]
+        from_template: Option<PathBuf>,
         #[clap(long)]
-        /// UNSAFE: Displays X-CSRF-Token to stdout.
-        unsafe_expose_csrf_token: bool,
+        clone: Option<ItemRef>,
+        #[clap(short = 'd', long)]
+        downloadables_dir: Option<PathBuf>,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Publish {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(long, value_parser = item::parse_rfc3339)]
+        at: Option<DateTime<FixedOffset>>,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Unpublish {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum VariationCommand {
+    List {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Attach {
+        #[clap(flatten)]
+        link: VariationLinkArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Detach {
+        #[clap(flatten)]
+        link: VariationLinkArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum GalleryCommand {
+    List {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Upload {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(short = 'p', long = "image", required = true)]
+        images: Vec<PathBuf>,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Delete {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(long = "image", required = true)]
+        images: Vec<String>,
+        #[clap(flatten)]
+        session: SessionArgs,
+    },
+    Arrange {
+        #[clap(flatten)]
+        item: ItemArgs,
+        #[clap(short = 'p', long = "image", required = true)]
+        images: Vec<PathBuf>,
         #[clap(long)]
-        /// UNSAFE: prints ALL header, including `cookie` header.
-        /// Only intended usage is debug purpose.
-        unsafe_expose_all_header: bool,
+        prune: bool,
+        #[clap(short = 'y', long)]
+        yes: bool,
+        #[clap(flatten)]
+        session: SessionArgs,
     },
 }
 
+/// ほとんどのサブコマンドが対象にする一つのアイテム
+#[derive(clap::Args)]
+struct ItemArgs {
+    #[clap(short = 'i', long)]
+    booth_item_id: ItemRef,
+}
+
+impl ItemArgs {
+    async fn resolve(&self, client: &ManageClient) -> Result<ItemId, ExecutionError> {
+        items::resolve(client, &self.booth_item_id).await
+    }
+}
+
+#[derive(clap::Args)]
+struct VariationLinkArgs {
+    #[clap(flatten)]
+    item: ItemArgs,
+    #[clap(long)]
+    variation: String,
+    #[clap(short = 'f', long = "file", required = true)]
+    files: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct SessionArgs {
+    #[clap(short = 't', long, long = "token")]
+    login_token: String,
+    #[clap(long, hide = true)]
+    localize_remote_error: bool,
+    #[clap(long)]
+    unsafe_expose_csrf_token: bool,
+    #[clap(long)]
+    unsafe_expose_all_header: bool,
+}
+
+impl SessionArgs {
+    /// 受け付けるだけで何もしないフラグが渡されたら、そのことを知らせる
+    pub(crate) fn warn_deprecated(&self) {
+        if self.localize_remote_error {
+            eprintln!("{}", message!("session.localize-remote-error-deprecated"));
+        }
+    }
+}
+
+#[derive(clap::Args)]
+pub(crate) struct QuotaGuardArgs {
+    #[clap(long)]
+    min_free: Option<ByteSize>,
+    #[clap(long, requires = "min_free")]
+    evict_oldest: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct LayoutArgs {
+    #[clap(long, default_value = "{item_id}/{file_name}")]
+    layout: Layout,
+    #[clap(long)]
+    extract: bool,
+}
+
 #[derive(Error, Debug)]
 pub(crate) enum ExecutionError {
-    #[error("Database error occured: {0}")]
+    #[error("{}", message!("error.database", error = .0))]
     Database(#[from] SQLite3ErrorWithCompare),
-    #[error("Incorrect usage of command line argument: {0}")]
+    #[error("{}", message!("error.argument", message = .0))]
     CommandLineArgumentValidation(String),
-    #[error("IO error: {0}")]
+    #[error("{}", message!("error.io", error = .0))]
     Io(#[from] std::io::Error),
-    #[error("Error occurred during fetching authorization token:")]
+    #[error("{}", message!("error.get-authorization-token", error = .0))]
     GetAuthorizationToken(#[from] GetAuthorizationTokenError),
-    #[error("HTTP request error: {0}")]
+    #[error("{}", message!("error.http", error = .0))]
     Http(#[from] reqwest::Error),
-    #[error("booth remote server error: {0}")]
+    #[error("{}", message!("error.booth", error = .0))]
     BoothUploadError(#[from] UploadError),
+    #[error("{}", message!("error.json", error = .0))]
+    Json(#[from] serde_json::Error),
+    #[error("{}", message!("error.csv", error = .0))]
+    Csv(#[from] csv::Error),
+    #[error("{}", message!("error.zip", error = .0))]
+    Zip(#[from] zip::result::ZipError),
+    #[error("{}", message!("error.quota-exceeded", left = pretty_size(*.left), required = pretty_size(*.required)))]
+    QuotaExceeded {
+        left: usize,
+        required: usize,
+    },
+    #[error("{}", message!("error.item-form", error = .0))]
+    ItemForm(#[from] ItemFormError),
+    #[error("{}", message!("error.gallery", error = .0))]
+    Gallery(#[from] GalleryError),
+    #[error("{}", message!("error.submit", error = .0))]
+    Submit(#[from] SubmitError),
+    #[error("{}", message!("error.stock", error = .0))]
+    Stock(#[from] StockError),
+    #[error("{}", message!("error.sale", error = .0))]
+    Sale(#[from] SaleError),
+    #[error("{}", message!("error.item-not-found", name = .0))]
+    ItemNotFound(String),
+    #[error("{}", message!("error.not-public", item = .0))]
+    NotPublic(ItemId),
+    #[error("{}", message!("error.verification-failed", item = .item, count = .count))]
+    VerificationFailed {
+        item: ItemId,
+        count: usize,
+    },
+    #[error("{}", message!("error.layout-collision", path = .0.display()))]
+    LayoutCollision(PathBuf),
+    #[error("{}", message!("error.download-failed", count = .count))]
+    DownloadFailed {
+        count: usize,
+    },
+    #[error("{}", message!("error.ambiguous-item", name = .name, count = .count))]
+    AmbiguousItem {
+        name: String,
+        count: usize,
+    },
 }
 
 #[derive(Error, Debug)]
 enum GetAuthorizationTokenError {
-    #[error("No tokens found")]
+    #[error("{}", message!("error.token-not-found"))]
     NotFound,
-    #[error("Multiple tokens (size: {count}) found")]
+    #[error("{}", message!("error.multiple-tokens", count = .count))]
     MultipleTokensFound {
         count: NonZeroUsize,
     },
@@ -90,153 +630,275 @@ pub(crate) enum Browser {
     #[strum(serialize = "chrome", serialize = "chromium", serialize = "vivaldi", serialize = "opera", serialize = "edge")]
     Chromium,
     #[strum(default)]
-    UnsupportedBrowser(String),
+    Unsupported(String),
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // Debug表現ではなく、利用者向けの表示をする
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
 #[allow(clippy::too_many_lines)]
 #[allow(clippy::similar_names)]
-#[tokio::main]
-async fn main() -> Result<(), ExecutionError> {
-    let clsc = CommandLineSubCommand::parse();
-    match clsc {
+async fn run() -> Result<(), ExecutionError> {
+    // ヘルプを訳すために、解析する前に言語を決める
+    let args = std::env::args_os().collect::<Vec<_>>();
+    locale::init(message::language_from_args(&args));
+    let matches = message::localize_command(CommandLine::command()).get_matches_from(args);
+    let command_line = CommandLine::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    debug_assert!(command_line.language.is_none_or(|x| x == locale::current()));
+
+    match command_line.subcommand {
         CommandLineSubCommand::GetAuthorizationToken { cookie_file, browser } => {
             sqlite::it(cookie_file, browser)?;
         }
         CommandLineSubCommand::Upload {
-            booth_item_id,
+            item,
             artifact_path,
-            login_token,
-            localize_remote_error,
-            unsafe_expose_csrf_token,
-            unsafe_expose_all_header,
+            variation,
+            quota_guard,
+            session,
         } => {
             if !artifact_path.exists() {
-                return Err(ExecutionError::CommandLineArgumentValidation("--artifact-path must point to existing path".to_string()))
+                return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.artifact-path-not-found")))
             }
 
             if artifact_path.is_dir() {
-                return Err(ExecutionError::CommandLineArgumentValidation("--artifact-path must point to file".to_string()))
-            }
-
-            let upload_url = format!("https://manage.booth.pm/items/{booth_item_id}/downloadables/");
-            eprintln!("url: {url}", url = &upload_url);
-            eprintln!("from: `{p}`", p = &artifact_path.display());
-
-            // reqwestのJarがなぜかcookieを渡さないので主導でmanipulateする
-            let baked_cookie = format!("_plaza_session_nktz7u={v}", v = &login_token);
-
-            let csrf_token = {
-                let client = reqwest::ClientBuilder::new()
-                    .gzip(true)
-                    .build()
-                    .unwrap();
-
-                // X-CSRF-Token対策
-                println!("Getting CSRF token");
-                let top_page = client.get(format!("https://manage.booth.pm/items/{booth_item_id}/edit"))
-                    .header("Accept", "text/html; charset=utf-8")
-                    .header("User-Agent", "KisaragiEffective/booth-upload-ci")
-                    .header("Cookie", &baked_cookie)
-                    .send()
-                    .await?
-                    .text()
-                    .await?;
-
-                let doc = select::document::Document::from(&*top_page);
-                let csrf_opt = doc
-                    .find(select::predicate::Name("meta").and(select::predicate::Attr("name", "csrf-token")))
-                    .find_map(|x| x.attr("content"));
-
-                if let Some(csrf) = csrf_opt {
-                    let csrf = csrf.to_owned();
-                    if unsafe_expose_csrf_token {
-                        println!("[CSRF] {csrf}");
-                    }
-                    csrf
-                } else {
-                    return Err(ExecutionError::BoothUploadError(UploadError::UnableToObtainCsrfToken))
-                }
-            };
-
-
-            let form = {
-                let form = Form::default();
-                let bytes = std::fs::read(&artifact_path)?;
-                let file_name = artifact_path.file_name()
-                    .map(|x| x.to_str().unwrap().to_string())
-                    .expect("upload file must have name");
-                // mime is inferred by remote
-                let upload = Part::bytes(bytes)
-                    .file_name(file_name);
-
-                form.part("downloadable[file]", upload)
-            };
-
-            let client = reqwest::ClientBuilder::new()
-                .gzip(true)
-                .build()
-                .unwrap();
-
-            let mut req = client.post(upload_url)
-                .multipart(form)
-                .header("Accept", "application/json")
-                .header("User-Agent", "KisaragiEffective/booth-upload-ci")
-                .header("Cookie", &baked_cookie)
-                // 欠けているとリクエストが正しくても422
-                .header("X-CSRF-Token", csrf_token);
-
-            cfg_if::cfg_if! {
-                if #[cfg(unix)] {
-                    if let Some(language_preference) = std::env::var_os("LANG") {
-                        use std::os::unix::ffi::OsStrExt;
-                        if localize_remote_error && language_preference.as_bytes().starts_with(b"ja_JP") {
-                            req = req.header("Accept-Language", "ja");
-                        }
-                    }
-                }
-            }
-
-            let res = req
-                .send()
-                .await?;
-
-            if unsafe_expose_all_header {
-                let http_version = res.version();
-                let http_status = res.status().as_u16();
-                println!("{http_version:?} {http_status}");
-                let headers = res.headers();
-                for (name, value) in headers {
-                    let value = if value.is_sensitive() {
-                        "《redacted》"
-                    } else {
-                        value.to_str().expect("received garbage in headers from remote server")
-                    };
-                    println!("{name}: {value}", name = name.as_str());
-                }
+                return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.artifact-path-is-dir")))
             }
 
+            let client = ManageClient::new(&session);
+            let booth_item_id = item.resolve(&client).await?;
+            let upload_url = client.url(&format!("/items/{booth_item_id}/downloadables/"));
+            eprintln!("{}", message!("upload.url", url = upload_url));
+            eprintln!("{}", message!("upload.from", path = artifact_path.display()));
 
-            #[allow(clippy::similar_names)]
-            let res = res
-                .json::<UploadResult>()
-                .await?;
+            let csrf_token = match &variation {
+                // アップロードした後に名前の間違いに気付くと、どこにも紐付かないファイルが容量を使ってしまう
+                Some(variation) => {
+                    let (form, csrf_token) = client.edit_form(booth_item_id).await?;
+                    ItemDraft::from_form(&form)?.variation_mut(variation)?;
+                    csrf_token
+                }
+                None => client.csrf_token(booth_item_id).await?,
+            };
+            let incoming = usize::try_from(artifact_path.metadata()?.len()).unwrap_or(usize::MAX);
+            quota::ensure_room(&client, booth_item_id, incoming, &quota_guard, &[], &csrf_token).await?;
+            let res = client.upload(booth_item_id, &artifact_path, &csrf_token).await?;
 
             match res {
                 UploadResult::Ok { storage, uploaded_file: file, .. } => {
-                    use crate::pretty_size::pretty_size;
-                    println!("uploaded as {name} ({size})", name = file.name, size = pretty_size(file.file_size));
-                    println!(
-                        "quota: (permitted = {permitted}) - (used = {used}) = (left = {left})",
+                    println!("{}", message!("upload.uploaded", name = file.name, size = pretty_size(file.file_size)));
+                    println!("{}", message!(
+                        "upload.quota",
                         permitted = storage.quota,
                         used = storage.usage,
-                        left = storage.left()
-                    );
+                        left = storage.left(),
+                    ));
+
+                    if let Some(variation) = variation {
+                        variation::link(&client, booth_item_id, &variation, &[file.id], true).await?;
+                    }
                 }
                 UploadResult::Err(error) => {
                     return Err(error.into())
                 }
             }
         }
+        CommandLineSubCommand::Sync {
+            item,
+            dir,
+            state_file,
+            prune,
+            yes,
+            dry_run,
+            quota_guard,
+            session,
+        } => {
+            let client = ManageClient::new(&session);
+            let booth_item_id = item.resolve(&client).await?;
+            sync::it(&client, booth_item_id, &dir, state_file, prune, yes, dry_run, &quota_guard).await?;
+        }
+        CommandLineSubCommand::Quota { item, session } => {
+            let client = ManageClient::new(&session);
+            let booth_item_id = item.resolve(&client).await?;
+            quota::it(&client, booth_item_id).await?;
+        }
+        CommandLineSubCommand::Items { command } => match command {
+            ItemsCommand::List { status, name_match, json, session } => {
+                let client = ManageClient::new(&session);
+                items::list(&client, status, name_match.as_deref(), json).await?;
+            }
+        },
+        CommandLineSubCommand::Item { command } => match command {
+            ItemCommand::Get { item, json, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                item::get(&client, booth_item_id, json).await?;
+            }
+            ItemCommand::Set { item, changes, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                item::set(&client, booth_item_id, changes).await?;
+            }
+            ItemCommand::Export { item, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                item::export(&client, booth_item_id).await?;
+            }
+            ItemCommand::Import { item, file, yes, dry_run, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                item::import(&client, booth_item_id, &file, yes, dry_run).await?;
+            }
+            ItemCommand::Create { from_template, clone, downloadables_dir, session } => {
+                let client = ManageClient::new(&session);
+                let template = match (from_template, clone) {
+                    (Some(path), _) => ItemTemplate::File(path),
+                    (None, Some(clone)) => ItemTemplate::Clone(items::resolve(&client, &clone).await?),
+                    (None, None) => unreachable!("clap requires either"),
+                };
+                item::create(&client, template, downloadables_dir.as_deref()).await?;
+            }
+            ItemCommand::Publish { item, at, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                item::publish(&client, booth_item_id, at).await?;
+            }
+            ItemCommand::Unpublish { item, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                item::unpublish(&client, booth_item_id).await?;
+            }
+        },
+        CommandLineSubCommand::Variation { command } => match command {
+            VariationCommand::List { item, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                variation::list(&client, booth_item_id).await?;
+            }
+            VariationCommand::Attach { link, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = link.item.resolve(&client).await?;
+                let files = variation::resolve_files(&client, booth_item_id, &link.files).await?;
+                variation::link(&client, booth_item_id, &link.variation, &files, true).await?;
+            }
+            VariationCommand::Detach { link, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = link.item.resolve(&client).await?;
+                let files = variation::resolve_files(&client, booth_item_id, &link.files).await?;
+                variation::link(&client, booth_item_id, &link.variation, &files, false).await?;
+            }
+        },
+        CommandLineSubCommand::Gallery { command } => match command {
+            GalleryCommand::List { item, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                gallery::list(&client, booth_item_id).await?;
+            }
+            GalleryCommand::Upload { item, images, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                gallery::upload(&client, booth_item_id, &images).await?;
+            }
+            GalleryCommand::Delete { item, images, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                gallery::delete(&client, booth_item_id, &images).await?;
+            }
+            GalleryCommand::Arrange { item, images, prune, yes, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                gallery::arrange_as(&client, booth_item_id, &images, prune, yes).await?;
+            }
+        },
+        CommandLineSubCommand::Verify { item, expect_files, manifest, session } => {
+            let client = ManageClient::new(&session);
+            let booth_item_id = item.resolve(&client).await?;
+            verify::it(&client, booth_item_id, &expect_files, manifest.as_deref()).await?;
+        }
+        CommandLineSubCommand::Orders { command } => match command {
+            OrdersCommand::Export { from, to, format, session } => {
+                let client = ManageClient::new(&session);
+                orders::export(&client, from, to, format).await?;
+            }
+        },
+        CommandLineSubCommand::Sales { command } => match command {
+            SalesCommand::Report { orders, by, from, to, compare_from, compare_to, format } => {
+                let compare = compare_from.zip(compare_to);
+                sales::it(&orders, by, (from, to), compare, format)?;
+            }
+        },
+        CommandLineSubCommand::Sale { command } => match command {
+            SaleCommand::Start { percent, items, until, ledger, yes, dry_run, session } => {
+                let client = ManageClient::new(&session);
+                sale::start(&client, &items, percent, until, ledger.as_deref(), yes, dry_run).await?;
+            }
+            SaleCommand::End { ledger, if_due, skip_conflicts, yes, dry_run, session } => {
+                let client = ManageClient::new(&session);
+                sale::end(&client, ledger.as_deref(), if_due, skip_conflicts, yes, dry_run).await?;
+            }
+        },
+        CommandLineSubCommand::Stock { command } => match command {
+            StockCommand::Get { item, variation, json, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                stock::get(&client, booth_item_id, variation.as_deref(), json).await?;
+            }
+            StockCommand::Set { item, variation, stock, decrement_on, session } => {
+                let client = ManageClient::new(&session);
+                let booth_item_id = item.resolve(&client).await?;
+                stock::set(&client, booth_item_id, &variation, stock, decrement_on).await?;
+            }
+        },
+        CommandLineSubCommand::Digest { state_file, low_stock, json, session } => {
+            let client = ManageClient::new(&session);
+            digest::it(&client, state_file.as_deref(), low_stock, json).await?;
+        }
+        CommandLineSubCommand::Messages { command } => match command {
+            MessagesCommand::List { unread, json, session } => {
+                messages::list(&ManageClient::new(&session), unread, json).await?;
+            }
+            MessagesCommand::Show { thread, json, session } => {
+                messages::show(&ManageClient::new(&session), thread, json).await?;
+            }
+            MessagesCommand::Reply { thread, body_file, session } => {
+                messages::reply(&ManageClient::new(&session), thread, &body_file).await?;
+            }
+        },
+        CommandLineSubCommand::Library { command } => match command {
+            LibraryCommand::List { json, session } => {
+                library::list(&LibraryClient::new(&session), json).await?;
+            }
+            LibraryCommand::Download { dir, layout, session } => {
+                library::download(&LibraryClient::new(&session), &dir, &layout).await?;
+            }
+            LibraryCommand::Sync { dir, index, layout, session } => {
+                library::sync(&LibraryClient::new(&session), &dir, index.as_deref(), &layout).await?;
+            }
+        },
+        CommandLineSubCommand::Wishlist { command } => match command {
+            WishlistCommand::List { json, session } => {
+                watch::wishlist(&LibraryClient::new(&session), json).await?;
+            }
+        },
+        CommandLineSubCommand::Watch { command } => match command {
+            WatchCommand::Prices { items, items_file, db, json } => {
+                watch::prices(&items, items_file.as_deref(), &db, json).await?;
+            }
+        },
+        CommandLineSubCommand::Public { command } => match command {
+            PublicCommand::Item { item, json } => {
+                public::item(&item, json).await?;
+            }
+        },
         /*
         CommandLineSubCommand::ListChoice { booth_item_id } => {
             TODO
@@ -0,0 +1,444 @@
+use std::path::Path;
+use chrono::{DateTime, FixedOffset};
+use reqwest::{Client, RequestBuilder, Response, Url};
+use reqwest::multipart::{Form, Part};
+use select::predicate::Predicate;
+use crate::{locale, ExecutionError, SessionArgs};
+use crate::message::message;
+use crate::booth::{DownloadableList, FileId, Gallery, GalleryImage, ImageId, ItemFormError, ItemId, ItemSummary, MessageThread, MessageThreadDetail, OrderRecord, UploadError, UploadResult, UploadedImage};
+use crate::form::{self, HtmlForm, SubmitError};
+
+const DEFAULT_BASE_URL: &str = "https://manage.booth.pm";
+pub const USER_AGENT: &str = "KisaragiEffective/booth-upload-ci";
+
+/// manage.booth.pmへのログイン済みセッション。
+pub struct ManageClient {
+    client: Client,
+    /// 送信したフォームが受け付けられたかをリダイレクトで見分けるので、自動では辿らない
+    form_client: Client,
+    base_url: String,
+    baked_cookie: String,
+    unsafe_expose_csrf_token: bool,
+    unsafe_expose_all_header: bool,
+}
+
+impl ManageClient {
+    pub fn new(session: &SessionArgs) -> Self {
+        session.warn_deprecated();
+        let client = reqwest::ClientBuilder::new()
+            .gzip(true)
+            .build()
+            .unwrap();
+
+        let form_client = reqwest::ClientBuilder::new()
+            .gzip(true)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        // テスト用のフィクスチャサーバーへ向けられるようにする
+        let base_url = std::env::var("BOOTH_MANAGE_BASE_URL")
+            .map_or_else(|_| DEFAULT_BASE_URL.to_string(), |x| x.trim_end_matches('/').to_string());
+
+        Self {
+            client,
+            form_client,
+            base_url,
+            // reqwestのJarがなぜかcookieを渡さないので主導でmanipulateする
+            baked_cookie: format!("_plaza_session_nktz7u={v}", v = &session.login_token),
+            unsafe_expose_csrf_token: session.unsafe_expose_csrf_token,
+            unsafe_expose_all_header: session.unsafe_expose_all_header,
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{base}{path}", base = &self.base_url)
+    }
+
+    fn request(&self, builder: RequestBuilder, accept: &str) -> RequestBuilder {
+        builder
+            .header("Accept", accept)
+            .header("Accept-Language", locale::current().accept_language())
+            .header("User-Agent", USER_AGENT)
+            .header("Cookie", &self.baked_cookie)
+    }
+
+    fn dump_headers(&self, res: &Response) {
+        if self.unsafe_expose_all_header {
+            let http_version = res.version();
+            let http_status = res.status().as_u16();
+            println!("{http_version:?} {http_status}");
+            let headers = res.headers();
+            for (name, value) in headers {
+                let value = if value.is_sensitive() {
+                    "《redacted》"
+                } else {
+                    value.to_str().expect("received garbage in headers from remote server")
+                };
+                println!("{name}: {value}", name = name.as_str());
+            }
+        }
+    }
+
+    /// アイテムの編集ページのHTML
+    pub async fn edit_page(&self, booth_item_id: ItemId) -> Result<String, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/items/{booth_item_id}/edit"))), "text/html; charset=utf-8")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.text().await?)
+    }
+
+    fn csrf_token_in(&self, page: &str) -> Result<String, ExecutionError> {
+        let doc = select::document::Document::from(page);
+        let csrf_opt = doc
+            .find(select::predicate::Name("meta").and(select::predicate::Attr("name", "csrf-token")))
+            .find_map(|x| x.attr("content"));
+
+        let Some(csrf) = csrf_opt else {
+            return Err(ExecutionError::BoothUploadError(UploadError::UnableToObtainCsrfToken))
+        };
+
+        let csrf = csrf.to_owned();
+        if self.unsafe_expose_csrf_token {
+            println!("[CSRF] {csrf}");
+        }
+        Ok(csrf)
+    }
+
+    /// 編集ページから`X-CSRF-Token`に使う値を取り出す。
+    pub async fn csrf_token(&self, booth_item_id: ItemId) -> Result<String, ExecutionError> {
+        // X-CSRF-Token対策
+        println!("{}", message!("manage.getting-csrf-token"));
+        let top_page = self.edit_page(booth_item_id).await?;
+        self.csrf_token_in(&top_page)
+    }
+
+    /// 編集ページのフォームと、それを送信するための`X-CSRF-Token`
+    pub async fn edit_form(&self, booth_item_id: ItemId) -> Result<(HtmlForm, String), ExecutionError> {
+        let page = self.edit_page(booth_item_id).await?;
+        let csrf = self.csrf_token_in(&page)?;
+        let form = HtmlForm::find(&page, &format!("/items/{booth_item_id}"))
+            .ok_or(ItemFormError::FormNotFound)?;
+
+        Ok((form, csrf))
+    }
+
+    /// 受け付けられればRailsはリダイレクトし、検証に失敗すればフォームのページを200で描き直す。リダイレクト先を返す。
+    async fn post_form(&self, form: &HtmlForm, csrf_token: &str) -> Result<Url, ExecutionError> {
+        let foreign = || SubmitError::ForeignAction(form.action.clone());
+        let base = Url::parse(&self.base_url).map_err(|_| foreign())?;
+        let canonical = Url::parse(DEFAULT_BASE_URL).map_err(|_| foreign())?;
+        let url = form.target(&base, &canonical).ok_or_else(foreign)?;
+
+        let res = self.request(self.form_client.post(url), "text/html; charset=utf-8")
+            .form(&form.fields)
+            .header("X-CSRF-Token", csrf_token)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        let res = res.error_for_status()?;
+        if res.status().is_redirection() {
+            let location = res.headers().get(reqwest::header::LOCATION)
+                .and_then(|x| x.to_str().ok())
+                .and_then(|x| res.url().join(x).ok())
+                .ok_or_else(|| ItemFormError::UnexpectedRedirect(res.url().to_string()))?;
+            if location.path().starts_with("/users/sign_in") {
+                return Err(SubmitError::SignedOut.into())
+            }
+
+            return Ok(location)
+        }
+
+        let errors = form::error_messages(&res.text().await?);
+        if errors.is_empty() {
+            Err(SubmitError::RejectedWithoutMessage.into())
+        } else {
+            Err(SubmitError::Rejected(errors.join(" / ")).into())
+        }
+    }
+
+    /// ブラウザと同じように`application/x-www-form-urlencoded`で送信する。
+    pub async fn submit_form(&self, form: &HtmlForm, csrf_token: &str) -> Result<(), ExecutionError> {
+        self.post_form(form, csrf_token).await?;
+
+        Ok(())
+    }
+
+    /// 新規作成ページのフォームをそのまま送信して下書きのアイテムを作る。作成後に移動する編集ページのURLからIDを得る。
+    pub async fn create_item(&self) -> Result<ItemId, ExecutionError> {
+        let res = self.request(self.client.get(self.url("/items/new")), "text/html; charset=utf-8")
+            .send()
+            .await?;
+        self.dump_headers(&res);
+        let page = res.error_for_status()?.text().await?;
+
+        let csrf = self.csrf_token_in(&page)?;
+        let form = HtmlForm::find(&page, "/items").ok_or(ItemFormError::FormNotFound)?;
+        let url = self.post_form(&form, &csrf).await?;
+
+        // /items/{id}/edit
+        let id = url.path_segments()
+            .and_then(|mut segments| segments.find_map(|x| x.parse().ok().and_then(ItemId::new)))
+            .ok_or_else(|| ItemFormError::UnexpectedRedirect(url.to_string()))?;
+
+        Ok(id)
+    }
+
+    pub async fn upload(&self, booth_item_id: ItemId, artifact_path: &Path, csrf_token: &str) -> Result<UploadResult, ExecutionError> {
+        let form = {
+            let form = Form::default();
+            let bytes = std::fs::read(artifact_path)?;
+            let file_name = artifact_path.file_name()
+                .map(|x| x.to_str().unwrap().to_string())
+                .expect("upload file must have name");
+            // mime is inferred by remote
+            let upload = Part::bytes(bytes)
+                .file_name(file_name);
+
+            form.part("downloadable[file]", upload)
+        };
+
+        let res = self.request(self.client.post(self.url(&format!("/items/{booth_item_id}/downloadables/"))), "application/json")
+            .multipart(form)
+            // 欠けているとリクエストが正しくても422
+            .header("X-CSRF-Token", csrf_token)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        let status = res.status();
+        let body = res.text().await?;
+        match serde_json::from_str::<UploadResult>(&body) {
+            Ok(result) => Ok(result),
+            // 422などはJSONではなくHTMLで返ってくる
+            Err(_) if status.is_client_error() || status.is_server_error() => {
+                Ok(UploadResult::Err(UploadError::Status { status: status.as_u16(), body }))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// アイテムにアップロード済みのファイルの一覧
+    pub async fn downloadables(&self, booth_item_id: ItemId) -> Result<DownloadableList, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/items/{booth_item_id}/downloadables"))), "application/json")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.json::<DownloadableList>().await?)
+    }
+
+    pub async fn delete_downloadable(&self, booth_item_id: ItemId, file_id: FileId, csrf_token: &str) -> Result<(), ExecutionError> {
+        let res = self.request(self.client.delete(self.url(&format!("/items/{booth_item_id}/downloadables/{file_id}"))), "application/json")
+            .header("X-CSRF-Token", csrf_token)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+        res.error_for_status()?;
+
+        Ok(())
+    }
+
+    /// 管理画面のアイテム一覧。`page`は1から
+    pub async fn items_page(&self, page: u32) -> Result<String, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/items?page={page}"))), "text/html; charset=utf-8")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.text().await?)
+    }
+
+    /// ショップのすべてのアイテム
+    pub async fn items(&self) -> Result<Vec<ItemSummary>, ExecutionError> {
+        let mut items = vec![];
+        for page in 1.. {
+            let (found, has_next) = ItemSummary::parse_page(&self.items_page(page).await?)?;
+            let empty = found.is_empty();
+            items.extend(found);
+            if !has_next || empty {
+                break
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// 管理画面の注文一覧。`page`は1から
+    pub async fn orders_page(&self, page: u32) -> Result<String, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/orders?page={page}"))), "text/html; charset=utf-8")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.text().await?)
+    }
+
+    /// `since`以降の注文。新しい順に並んでいるので、`since`より古い注文が出てきたページで止める。
+    pub async fn orders_since(&self, since: DateTime<FixedOffset>) -> Result<Vec<OrderRecord>, ExecutionError> {
+        let mut records = vec![];
+        for page in 1.. {
+            let (found, has_next) = OrderRecord::parse_page(&self.orders_page(page).await?)?;
+            let reached = found.is_empty() || found.iter().any(|x| x.ordered_at < since);
+            records.extend(found.into_iter().filter(|x| x.ordered_at >= since));
+            if !has_next || reached {
+                break
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// メッセージの一覧。`page`は1から
+    pub async fn messages_page(&self, page: u32) -> Result<String, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/messages?page={page}"))), "text/html; charset=utf-8")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.text().await?)
+    }
+
+    /// すべてのやりとり
+    pub async fn message_threads(&self) -> Result<Vec<MessageThread>, ExecutionError> {
+        let mut threads = vec![];
+        for page in 1.. {
+            let (found, has_next) = MessageThread::parse_page(&self.messages_page(page).await?)?;
+            let empty = found.is_empty();
+            threads.extend(found);
+            if !has_next || empty {
+                break
+            }
+        }
+
+        Ok(threads)
+    }
+
+    /// `since`以降に更新されたやりとり。一覧は更新の新しい順なので、それより古いものが出たページで止める
+    pub async fn message_threads_since(&self, since: DateTime<FixedOffset>) -> Result<Vec<MessageThread>, ExecutionError> {
+        let mut threads = vec![];
+        for page in 1.. {
+            let (found, has_next) = MessageThread::parse_page(&self.messages_page(page).await?)?;
+            let reached = found.is_empty() || found.iter().any(|x| x.updated_at < since);
+            threads.extend(found.into_iter().filter(|x| x.updated_at >= since));
+            if !has_next || reached {
+                break
+            }
+        }
+
+        Ok(threads)
+    }
+
+    async fn message_thread_page(&self, thread_id: u64) -> Result<String, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/messages/{thread_id}"))), "text/html; charset=utf-8")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.text().await?)
+    }
+
+    pub async fn message_thread(&self, thread_id: u64) -> Result<MessageThreadDetail, ExecutionError> {
+        Ok(MessageThreadDetail::parse(thread_id, &self.message_thread_page(thread_id).await?)?)
+    }
+
+    /// やりとりのページにある返信のフォームから送る
+    pub async fn reply_message(&self, thread_id: u64, body: &str) -> Result<(), ExecutionError> {
+        let page = self.message_thread_page(thread_id).await?;
+        let csrf = self.csrf_token_in(&page)?;
+        // 既読にするフォームなども同じスレッドのURLへ送るので、本文の入力欄で見分ける
+        let mut form = HtmlForm::find_with_field(&page, "message[body]").ok_or(ItemFormError::FormNotFound)?;
+        form.set("message[body]", body);
+
+        self.submit_form(&form, &csrf).await
+    }
+
+    /// ギャラリーの画像の一覧
+    pub async fn gallery(&self, booth_item_id: ItemId) -> Result<Gallery, ExecutionError> {
+        let res = self.request(self.client.get(self.url(&format!("/items/{booth_item_id}/images"))), "application/json")
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.json::<Gallery>().await?)
+    }
+
+    /// ギャラリーの画像の中身。画像は管理画面とは別のホストから配信されるので、セッションのcookieは付けない。
+    pub async fn image_content(&self, image: &GalleryImage) -> Result<Vec<u8>, ExecutionError> {
+        let res = self.client.get(&image.url)
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        Ok(res.error_for_status()?.bytes().await?.to_vec())
+    }
+
+    /// ギャラリーの末尾に画像を追加する。
+    pub async fn upload_image(&self, booth_item_id: ItemId, name: &str, bytes: Vec<u8>, csrf_token: &str) -> Result<GalleryImage, ExecutionError> {
+        let form = Form::default().part("image[file]", Part::bytes(bytes).file_name(name.to_string()));
+
+        let res = self.request(self.client.post(self.url(&format!("/items/{booth_item_id}/images"))), "application/json")
+            .multipart(form)
+            .header("X-CSRF-Token", csrf_token)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+
+        let status = res.status();
+        let body = res.text().await?;
+        match serde_json::from_str::<UploadedImage>(&body) {
+            Ok(uploaded) => Ok(uploaded.image),
+            // ダウンロードファイルと同じ形で拒否理由が返ってくる
+            Err(_) => match serde_json::from_str::<UploadError>(&body) {
+                Ok(error) => Err(error.into()),
+                Err(_) if status.is_client_error() || status.is_server_error() => {
+                    Err(UploadError::Status { status: status.as_u16(), body }.into())
+                }
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    pub async fn delete_image(&self, booth_item_id: ItemId, image_id: ImageId, csrf_token: &str) -> Result<(), ExecutionError> {
+        let res = self.request(self.client.delete(self.url(&format!("/items/{booth_item_id}/images/{image_id}"))), "application/json")
+            .header("X-CSRF-Token", csrf_token)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+        res.error_for_status()?;
+
+        Ok(())
+    }
+
+    /// ギャラリーを`order`の順に並べ替える。`order`には全ての画像を含める。
+    pub async fn reorder_images(&self, booth_item_id: ItemId, order: &[ImageId], csrf_token: &str) -> Result<(), ExecutionError> {
+        let ids = order.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let res = self.request(self.client.patch(self.url(&format!("/items/{booth_item_id}/images/order"))), "application/json")
+            .form(&ids.iter().map(|id| ("image_ids[]", id.as_str())).collect::<Vec<_>>())
+            .header("X-CSRF-Token", csrf_token)
+            .send()
+            .await?;
+
+        self.dump_headers(&res);
+        res.error_for_status()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+//! 利用者に見せる文字列のカタログ。キーは`分類.名前`の形で、ヘルプは`help.<サブコマンド>.<引数>`。
+
+use std::ffi::OsString;
+use std::fmt::Display;
+use clap::Command;
+use crate::locale::{self, Language};
+
+mod en;
+mod ja;
+
+type Catalog = &'static [(&'static str, &'static str)];
+
+const fn catalog(language: Language) -> Catalog {
+    match language {
+        Language::Japanese => ja::MESSAGES,
+        _ => en::MESSAGES,
+    }
+}
+
+fn lookup(language: Language, key: &str) -> Option<&'static str> {
+    catalog(language).iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+/// 現在の言語でのメッセージ。訳がなければ英語、それもなければキーそのものを返す。
+pub fn get(key: &'static str) -> &'static str {
+    lookup(locale::current(), key)
+        .or_else(|| lookup(Language::English, key))
+        .unwrap_or(key)
+}
+
+/// メッセージの`{name}`を置き換える。
+pub fn format(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    render(get(key), args)
+}
+
+/// 対応する引数がない`{...}`はそのまま残す。
+fn render(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut formatted = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        formatted.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        let replaced = after_open.find('}').and_then(|close| {
+            let name = &after_open[..close];
+            args.iter().find(|(n, _)| *n == name).map(|(_, value)| (close, value))
+        });
+
+        if let Some((close, value)) = replaced {
+            formatted.push_str(&value.to_string());
+            rest = &after_open[close + 1..];
+        } else {
+            formatted.push('{');
+            rest = after_open;
+        }
+    }
+    formatted.push_str(rest);
+
+    formatted
+}
+
+/// `message!("sync.uploading", name = local.name)`のように使う。
+macro_rules! message {
+    ($key:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::message::format($key, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*])
+    };
+}
+
+pub(crate) use message;
+
+/// 解析する前に`--language`を拾う。値が不正な場合はclapに任せる。
+pub fn language_from_args(args: &[OsString]) -> Option<Language> {
+    let mut args = args.iter().filter_map(|x| x.to_str());
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break
+        }
+
+        if let Some(value) = arg.strip_prefix("--language=") {
+            return value.parse().ok()
+        }
+
+        if arg == "--language" {
+            return args.next().and_then(|value| value.parse().ok())
+        }
+    }
+
+    None
+}
+
+fn help_key(path: &str, id: &str) -> String {
+    if path.is_empty() {
+        format!("help.{id}")
+    } else {
+        format!("help.{path}.{id}")
+    }
+}
+
+fn lookup_help(language: Language, command_path: &str, arg_id: &str) -> Option<&'static str> {
+    // 複数のサブコマンドで共有している引数は`help.arg.<引数>`にまとめてある
+    lookup(language, &help_key(command_path, arg_id))
+        .or_else(|| lookup(language, &help_key("arg", arg_id)))
+}
+
+/// 最初の段落を短いヘルプに、全体を`--help`で表示する長いヘルプに使う。
+fn split_help(text: &'static str) -> (&'static str, &'static str) {
+    (text.split("\n\n").next().unwrap_or(text), text)
+}
+
+/// 入れ子になったサブコマンドにも付く`help`は、どこにあっても`help.help`を使う。
+fn sub_command_path(path: &str, name: &str) -> String {
+    if path.is_empty() || name == "help" { name.to_string() } else { format!("{path}.{name}") }
+}
+
+/// clapのヘルプを現在の言語に置き換える。
+pub fn localize_command(mut command: Command) -> Command {
+    command.build();
+    let language = locale::current();
+    localize_command_recursive(command, language, "")
+}
+
+fn localize_command_recursive(mut command: Command, language: Language, path: &str) -> Command {
+    let about_key = if path.is_empty() { "help.about".to_string() } else { format!("help.{path}") };
+    if let Some(about) = lookup(language, &about_key) {
+        let (short, long) = split_help(about);
+        command = command.about(short).long_about(long);
+    }
+
+    if let (Some(usage), Some(commands), Some(options)) = (
+        lookup(language, "help.heading.usage"),
+        lookup(language, "help.heading.commands"),
+        lookup(language, "help.heading.options"),
+    ) {
+        command = command
+            .help_template(format!("{{before-help}}{{about-with-newline}}\n{usage} {{usage}}\n\n{{all-args}}{{after-help}}"))
+            .subcommand_help_heading(commands);
+
+        let ids = command.get_arguments().filter(|a| a.get_help_heading().is_none()).map(|a| a.get_id().clone()).collect::<Vec<_>>();
+        for id in ids {
+            command = command.mut_arg(id, |a| a.help_heading(options));
+        }
+    }
+
+    let ids = command.get_arguments().map(|a| a.get_id().to_string()).collect::<Vec<_>>();
+    for id in ids {
+        let help = match id.as_str() {
+            "help" => lookup(language, "help.flag.help"),
+            "version" => lookup(language, "help.flag.version"),
+            _ => lookup_help(language, path, &id),
+        };
+
+        if let Some(help) = help {
+            let (short, long) = split_help(help);
+            command = command.mut_arg(id, |a| a.help(short).long_help(long));
+        }
+    }
+
+    // `help`サブコマンドの下には他のサブコマンドの名前だけが並ぶ
+    let names = command.get_subcommands().map(|c| c.get_name().to_string()).filter(|_| path != "help").collect::<Vec<_>>();
+    for name in names {
+        let sub_path = sub_command_path(path, &name);
+        command = command.mut_subcommand(name, |c| localize_command_recursive(c, language, &sub_path));
+    }
+
+    command
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+    use std::ffi::OsString;
+    use clap::{Command, CommandFactory};
+    use crate::CommandLine;
+    use crate::locale::Language;
+    use crate::message::{catalog, help_key, sub_command_path, language_from_args, lookup, lookup_help, render};
+
+    const LANGUAGES: [Language; 2] = [Language::Japanese, Language::English];
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template.split('{').skip(1).filter_map(|x| x.split_once('}').map(|(name, _)| name)).collect()
+    }
+
+    #[test]
+    fn every_key_is_translated() {
+        for language in LANGUAGES {
+            let keys = catalog(language).iter().map(|(k, _)| *k).collect::<Vec<_>>();
+            let unique = keys.iter().collect::<BTreeSet<_>>();
+            assert_eq!(keys.len(), unique.len(), "duplicate key in {language:?}");
+
+            for other in LANGUAGES {
+                for (key, template) in catalog(language) {
+                    let translated = lookup(other, key).unwrap_or_else(|| panic!("`{key}` is missing in {other:?}"));
+                    assert_eq!(placeholders(template), placeholders(translated), "placeholders of `{key}` differ");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_used_key_exists() {
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut pending = vec![src];
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue
+                }
+
+                let source = std::fs::read_to_string(&path).unwrap();
+                for marker in ["message!(\"", "message::get(\""] {
+                    for occurrence in source.split(marker).skip(1) {
+                        let key = occurrence.split('"').next().unwrap();
+                        for language in LANGUAGES {
+                            assert!(lookup(language, key).is_some(), "`{key}` used in {path} is missing in {language:?}", path = path.display());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn assert_help_exists(command: &Command, path: &str) {
+        for language in LANGUAGES {
+            let about_key = if path.is_empty() { "help.about".to_string() } else { format!("help.{path}") };
+            assert!(lookup(language, &about_key).is_some(), "`{about_key}` is missing in {language:?}");
+
+            for arg in command.get_arguments().filter(|a| !matches!(a.get_id().as_str(), "help" | "version")) {
+                let id = arg.get_id().as_str();
+                assert!(lookup_help(language, path, id).is_some(), "`{key}` is missing in {language:?}", key = help_key(path, id));
+            }
+        }
+
+        for sub in command.get_subcommands().filter(|_| path != "help") {
+            let sub_path = sub_command_path(path, sub.get_name());
+            assert_help_exists(sub, &sub_path);
+        }
+    }
+
+    #[test]
+    fn every_argument_has_help() {
+        let mut command = CommandLine::command();
+        command.build();
+        assert_help_exists(&command, "");
+    }
+
+    #[test]
+    fn render_placeholders() {
+        assert_eq!(render("uploading {name} ({size})", &[("name", &"a.zip"), ("size", &"1B")]), "uploading a.zip (1B)");
+        assert_eq!(render("{unknown} {", &[]), "{unknown} {");
+    }
+
+    #[test]
+    fn language_argument() {
+        let args = |x: &[&str]| x.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(language_from_args(&args(&["kbu", "--language", "ja", "upload"])), Some(Language::Japanese));
+        assert_eq!(language_from_args(&args(&["kbu", "upload", "--language=en"])), Some(Language::English));
+        assert_eq!(language_from_args(&args(&["kbu", "upload"])), None);
+    }
+}
@@ -0,0 +1,376 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // clap
+    ("help.about", "Utility around booth.pm, developed by Kisaragi Marine.\n\nThis project is not related, developed, nor affiliated by pixiv inc. Please refer to <https://policies.pixiv.net/> and <https://policies.pixiv.net/#booth> before use."),
+    ("help.heading.usage", "Usage:"),
+    ("help.heading.commands", "Commands"),
+    ("help.heading.options", "Options"),
+    ("help.flag.help", "Print help"),
+    ("help.flag.version", "Print version"),
+    ("help.language", "Language of messages, and `Accept-Language` sent to BOOTH. accepts `ja`, `en`, `ko`, `zh-cn` or `zh-tw`.\n\nIf omitted, `BOOTH_LANGUAGE`, `LC_ALL`, `LC_MESSAGES` and `LANG` are used in this order. On Windows, the user locale is used if none of them is set."),
+    ("help.arg.language", "Language of messages, and `Accept-Language` sent to BOOTH. accepts `ja`, `en`, `ko`, `zh-cn` or `zh-tw`."),
+    ("help.arg.booth_item_id", "Your item's id or URL, e.g. `3519955`, <https://booth.pm/ja/items/3519955> or <https://shopname.booth.pm/items/3519955>.\n\n`name:<name>` looks the item up by its exact name."),
+    ("help.arg.login_token", "Can be grabbed by `get-authorization-token` subcommand."),
+    ("help.arg.localize_remote_error", "Deprecated: `Accept-Language` is always sent. Use `--language` to change it."),
+    ("help.arg.unsafe_expose_csrf_token", "UNSAFE: Displays X-CSRF-Token to stdout."),
+    ("help.arg.unsafe_expose_all_header", "UNSAFE: prints ALL header, including `cookie` header.\n\nOnly intended usage is debug purpose."),
+    ("help.arg.min_free", "Aborts before uploading if less than this size would be left afterwards. e.g. `500MiB`"),
+    ("help.arg.evict_oldest", "Deletes the oldest downloadables of the item matching this pattern (`*` and `?` are wildcards) until `--min-free` is satisfied, instead of aborting."),
+    ("help.help", "Print this message or the help of the given subcommand(s)"),
+    ("help.help.subcommand", "Print help for the subcommand(s)"),
+    ("help.get-authorization-token", "Prints the login token saved in your browser's cookie."),
+    ("help.get-authorization-token.cookie_file", "Path to `cookies.sqlite` if firefox, `Cookies` if chromium."),
+    ("help.get-authorization-token.browser", "accepts `firefox` or `chromium`.\n\nInternet Explorer, Safari, Sleipnir, Lunaspace, legacy Edge and legacy Opera are unsupported."),
+    ("help.upload", "Uploads a file to the item's downloadables."),
+    ("help.upload.artifact_path", "Your local path to be uploaded."),
+    ("help.sync", "Reconciles files in a local directory with the item's downloadables.\n\nFiles are compared by name, size and the content hash recorded on previous sync."),
+    ("help.sync.dir", "Local directory to be synchronized. Subdirectories are ignored."),
+    ("help.sync.state_file", "Where to record uploaded contents. Defaults to `.kisaragi-booth-sync.json` in `--dir`."),
+    ("help.sync.prune", "Deletes remote files which no longer exist in `--dir`."),
+    ("help.sync.yes", "Skips confirmation before deleting remote files."),
+    ("help.sync.dry_run", "Only prints the plan."),
+    ("help.quota", "Shows disk quota of your shop and the downloadables of the item."),
+    ("help.item", "Reads or edits the item's metadata on its edit page."),
+    ("help.item.get", "Prints name, description, tags, category, prices, age restriction and publish state of the item."),
+    ("help.arg.json", "Prints as JSON."),
+    ("help.item.set", "Updates the given fields of the item. Other fields are left untouched."),
+    ("help.item.set.name", "New name of the item."),
+    ("help.item.set.description", "New description of the item."),
+    ("help.item.set.description_file", "Reads the new description from this file."),
+    ("help.item.set.tags", "Comma-separated tags, replacing the current ones. Pass an empty string to clear."),
+    ("help.item.set.category", "Category id of the item."),
+    ("help.item.set.price", "Price in yen, as `<variation id or name>=<price>`. Without `<variation>=`, applies to every variation. Can be repeated."),
+    ("help.item.set.age_restriction", "`all` or `r18`."),
+    ("help.item.set.state", "`draft`, `private` or `public`."),
+    ("help.upload.variation", "Also attaches the uploaded file to this variation, by id or name."),
+    ("help.variation", "Lists the item's variations, and links downloadables to them."),
+    ("help.variation.list", "Prints id, name, price and type of each variation with its downloadables."),
+    ("help.variation.attach", "Makes the downloadables available to buyers of the variation."),
+    ("help.variation.detach", "Makes the downloadables no longer available to buyers of the variation. The files are not deleted."),
+    ("help.arg.variation", "Id or name of the variation."),
+    ("help.arg.files", "Id or name of the downloadable. Can be repeated."),
+    ("help.item.publish", "Makes the item public."),
+    ("help.item.publish.at", "Publishes at this time instead, e.g. `2026-01-01T00:00:00+09:00`.\n\nUses the reservation of BOOTH if the item supports it, otherwise waits until the time."),
+    ("help.item.unpublish", "Makes the item private, cancelling the reservation if any."),
+    ("help.gallery", "Manages images in the item's gallery."),
+    ("help.gallery.list", "Prints the images in the gallery, in display order."),
+    ("help.gallery.upload", "Appends images to the gallery. All images are validated before uploading any of them."),
+    ("help.gallery.upload.images", "Local path to a JPEG, PNG or GIF image. Can be repeated."),
+    ("help.gallery.delete", "Deletes images from the gallery."),
+    ("help.gallery.delete.images", "Id or name of the image. Can be repeated."),
+    ("help.gallery.arrange", "Orders the gallery as the given local images, uploading the ones not in the gallery yet.\n\nImages are matched by file name. When the content of a matched image differs, the local image is uploaded in its place and the old one is deleted."),
+    ("help.gallery.arrange.images", "Local path to a JPEG, PNG or GIF image, in display order. Can be repeated."),
+    ("help.gallery.arrange.prune", "Deletes images which are not given. Otherwise they are placed after the given ones."),
+    ("help.gallery.arrange.yes", "Skips confirmation before deleting images."),
+    ("help.item.export", "Prints the whole editable state of the item as JSON, to be restored by `item import`."),
+    ("help.item.import", "Applies a file written by `item export` to the item, showing the changes before writing.\n\nImages and downloadables are only reordered and checked, as their contents are not exported."),
+    ("help.item.import.file", "File written by `item export`."),
+    ("help.item.import.yes", "Skips confirmation before writing."),
+    ("help.item.import.dry_run", "Only prints the changes."),
+    ("help.item.create", "Creates a new draft item, and prints its id."),
+    ("help.item.create.from_template", "File written by `item export`."),
+    ("help.item.create.clone", "Id of an existing item to be copied."),
+    ("help.item.create.downloadables_dir", "Directory containing the downloadables of the template. Files are matched by name."),
+    ("help.items", "Lists items in your shop."),
+    ("help.items.list", "Prints id, name, status, price range, variation count and downloadable count of every item."),
+    ("help.items.list.status", "Only lists items in this state: `draft`, `private` or `public`."),
+    ("help.items.list.name_match", "Only lists items whose name matches this pattern. `*` and `?` are wildcards."),
+    ("help.public", "Reads what buyers see on booth.pm. No login is needed."),
+    ("help.public.item", "Prints name, price, variations, stock, shop, images, tags, wish count and published date of the item.\n\nThe base URL can be changed by `BOOTH_PUBLIC_BASE_URL`."),
+    ("help.public.item.item", "Item's id or URL."),
+    ("help.verify", "Checks that the item is public with the expected downloadables, and that the public page matches the manage site.\n\nExits with failure if anything differs."),
+    ("help.verify.expect_files", "File name which must be uploaded, or a local file which must be uploaded with the same name and size. A bare name is checked for its size only when the manifest gives one. Can be repeated."),
+    ("help.verify.manifest", "File written by `item export`, describing the expected name, prices and downloadables."),
+    ("help.orders", "Reads orders of your shop."),
+    ("help.orders.export", "Writes orders in the period to stdout, one record per ordered item, oldest first. Dates are in JST."),
+    ("help.arg.from", "First day of the period, e.g. `2026-09-01`."),
+    ("help.arg.to", "Last day of the period, inclusive, e.g. `2026-09-30`."),
+    ("help.orders.export.format", "`csv` or `json`."),
+    ("help.sales", "Aggregates orders written by `orders export`, without network access."),
+    ("help.sales.report", "Prints quantity, revenue, fees and net payout grouped by item, variation, month or weekday.\n\nCancelled orders are excluded, and fees are estimated as 5.6% + ¥22 per order."),
+    ("help.sales.report.orders", "File written by `orders export`, in CSV or JSON (by `.json` extension)."),
+    ("help.sales.report.by", "`item`, `variation`, `month` or `weekday`."),
+    ("help.sales.report.compare_from", "First day of the period to be compared with. Cannot be used with `--by month`."),
+    ("help.sales.report.compare_to", "Last day of the period to be compared with, inclusive."),
+    ("help.sales.report.format", "`table` or `csv`."),
+    ("help.library", "Lists and downloads items you bought, including gifts."),
+    ("help.library.list", "Lists purchased and gifted items with their downloadable files."),
+    ("help.library.download", "Downloads every downloadable file under `--dir`, with item details and purchase date in `booth.json` next to them (`booth-<item id>.json` when the layout puts files of several items in one directory).\n\nFiles already present with the same size are skipped, and interrupted downloads are resumed. If some files fail, the rest are still downloaded and the command exits with failure at the end."),
+    ("help.library.download.dir", "Directory to save files in."),
+    ("help.library.sync", "Downloads only files that are new or changed since the last run, and prints which items got updates.\n\nFiles removed from the library are kept on disk."),
+    ("help.library.sync.dir", "Directory to save files in."),
+    ("help.library.sync.index", "Database file (sqlite3) remembering downloaded files. Defaults to `.booth-library.sqlite3` in `--dir`."),
+    ("help.arg.layout", "Where to save each file under `--dir`, e.g. `{shop}/{item_name} ({item_id})/{file_name}`.\n\n`{shop}`, `{item_name}`, `{item_id}`, `{file_name}` and `{file_id}` can be used."),
+    ("help.arg.extract", "Extracts downloaded zip archives into a folder of the same name next to them."),
+    ("help.wishlist", "Reads your wish list."),
+    ("help.wishlist.list", "Lists items in your wish list. Each line starts with the item id, so it can be passed to `watch prices --items-file -`."),
+    ("help.watch", "Records public information on each run and reports what changed since the previous run."),
+    ("help.watch.prices", "Records prices, stock and status of variations, and prints price drops, price rises, stock and status changes one per line.\n\nNothing is printed when nothing changed. No login is needed."),
+    ("help.watch.prices.items", "Item ids or URLs to watch."),
+    ("help.watch.prices.items_file", "File with an item id or URL at the start of each line. `-` reads the standard input."),
+    ("help.watch.prices.db", "Database file (sqlite3) keeping prices seen so far."),
+    ("help.watch.prices.json", "Prints each change as a line of JSON."),
+    ("help.messages", "Reads and replies to messages from buyers."),
+    ("help.messages.list", "Lists message threads, newest first."),
+    ("help.messages.list.unread", "Lists only unread threads."),
+    ("help.messages.show", "Prints all messages in a thread, oldest first."),
+    ("help.arg.thread", "Id of the thread, as printed by `messages list`."),
+    ("help.messages.reply", "Sends a reply to a thread."),
+    ("help.messages.reply.body_file", "Text file with the reply. `-` reads the standard input."),
+    ("help.digest", "Summarizes new orders, messages, wish counts, low stock and quota usage since the last run.\n\nThe first run covers the last 24 hours. BOOTH has no reviews, so wish counts are the only feedback reported. Wish counts come from public pages, so they are not reported for private or draft items; their stock is read from the edit page."),
+    ("help.digest.state_file", "Where to record the last run. Defaults to `.kisaragi-booth-digest.json` in the current directory."),
+    ("help.digest.low_stock", "Reports variations with stock at or below this number."),
+    ("help.stock", "Reads and updates stock of variations."),
+    ("help.stock.get", "Prints stock of each variation. `-` means the variation does not track stock."),
+    ("help.stock.set", "Sets stock of a variation."),
+    ("help.stock.set.stock", "New stock."),
+    ("help.stock.set.decrement_on", "Stock your script read before computing the new stock. Items sold since then are subtracted, and nothing is written if the stock has been increased in the meantime."),
+    ("help.sale", "Runs a discount sale over your items, keeping the original prices in a local ledger."),
+    ("help.sale.start", "Discounts every variation of the items, and records the original prices in the ledger."),
+    ("help.sale.start.percent", "Discount in percent, from 1 to 99. Prices are rounded down to yen, and never go below ¥100."),
+    ("help.sale.start.items", "Comma separated items to discount. `all` means every public item."),
+    ("help.sale.start.until", "Last day of the sale, e.g. `2026-11-03`. `sale end --if-due` restores the prices after this day."),
+    ("help.sale.start.ledger", "Where to record the original prices. Defaults to `.kisaragi-booth-sale.json` in the current directory."),
+    ("help.sale.start.yes", "Skips confirmation before writing."),
+    ("help.sale.start.dry_run", "Only prints the plan."),
+    ("help.sale.end", "Restores the original prices from the ledger. Nothing is written if any price has been changed since the sale started, unless `--skip-conflicts` is given."),
+    ("help.sale.end.ledger", "Where the original prices are recorded. Defaults to `.kisaragi-booth-sale.json` in the current directory."),
+    ("help.sale.end.if_due", "Does nothing until the day given by `--until` has passed. Intended for scheduled jobs."),
+    ("help.sale.end.skip_conflicts", "Leaves prices changed since the sale started as they are, drops them from the ledger, and restores the rest."),
+    ("help.sale.end.yes", "Skips confirmation before writing."),
+    ("help.sale.end.dry_run", "Only prints the plan."),
+    // ExecutionError
+    ("error.database", "Database error occured: {error}"),
+    ("error.argument", "Incorrect usage of command line argument: {message}"),
+    ("error.io", "IO error: {error}"),
+    ("error.get-authorization-token", "Error occurred during fetching authorization token: {error}"),
+    ("error.http", "HTTP request error: {error}"),
+    ("error.booth", "booth remote server error: {error}"),
+    ("error.json", "JSON error: {error}"),
+    ("error.quota-exceeded", "not enough space left on BOOTH: {left} left, but {required} required"),
+    ("error.token-not-found", "No tokens found"),
+    ("error.multiple-tokens", "Multiple tokens (size: {count}) found"),
+    ("error.unsupported-language", "unsupported language: {language} (expected one of ja, en, ko, zh-cn, zh-tw)"),
+    ("error.size.empty", "size must not be empty"),
+    ("error.size.invalid-number", "invalid number: {number}"),
+    ("error.size.unknown-unit", "unknown unit: {unit} (expected one of B, KiB, MiB, GiB)"),
+    ("error.size.too-large", "size must not exceed 100GiB"),
+    ("error.item-form", "unable to read the edit form of the item: {error}"),
+    ("error.item-form.not-found", "the edit form was not found. the token may be expired."),
+    ("error.item-form.missing-field", "`{field}` is missing"),
+    ("error.item-form.invalid-value", "`{field}` has unexpected value: {value}"),
+    ("error.item-form.unknown-variation", "no such variation: {variation}"),
+    ("error.item.invalid-price", "invalid price: {price} (expected `<variation>=<yen>` or `<yen>`)"),
+    ("validation.unknown-downloadable", "no such downloadable: {file}"),
+    ("error.item.invalid-time", "invalid time: {time} (expected RFC 3339, e.g. `2026-01-01T00:00:00+09:00`): {error}"),
+    ("error.gallery", "invalid image: {error}"),
+    ("error.gallery.unsupported-format", "`{path}` is not a JPEG, PNG or GIF image"),
+    ("error.gallery.too-large", "`{path}` is too large: {size} (up to {max})"),
+    ("error.gallery.dimensions", "`{path}` is {width}x{height}, but each side must be between {min} and {max} pixels"),
+    ("error.gallery.duplicate-name", "multiple images are named {name}"),
+    ("validation.unknown-image", "no such image: {image}"),
+    ("error.item-form.unexpected-redirect", "unable to find the id of the created item in {url}"),
+    ("error.item-ref.invalid-id", "invalid item id: {id} (expected a positive number)"),
+    ("error.item-ref.unsupported-url", "not an item URL of BOOTH: {url}"),
+    ("error.item-not-found", "no item is named {name}"),
+    ("error.layout-collision", "more than one file would be saved to {path}. add {item_id} or {file_id} to --layout"),
+    ("error.download-failed", "failed to download {count} file(s)"),
+    ("error.ambiguous-item", "{count} items are named {name}. specify it by id"),
+    ("validation.public-item-by-name", "`name:` can not be used with public. specify the item by id or URL"),
+    ("error.not-public", "item {item} is not public"),
+    ("error.verification-failed", "verification of item {item} failed with {count} problem(s)"),
+    ("error.csv", "CSV error: {error}"),
+    ("validation.period-reversed", "--from ({from}) must not be after --to ({to})"),
+    ("error.zip", "zip error: {error}"),
+    ("error.layout.unknown-placeholder", "unknown placeholder `{{placeholder}}` in layout"),
+    ("error.layout.unclosed-brace", "layout has an unclosed brace"),
+    ("error.layout.empty-segment", "layout must not contain empty, `.` or `..` directories"),
+    ("error.layout.no-file-name", "the last part of layout must contain `{file_name}` or `{file_id}`"),
+    ("validation.watch-item-by-name", "`name:` can not be used with watch. specify the item by id or URL"),
+    ("validation.empty-message-body", "the reply is empty"),
+    ("error.stock", "stock error: {error}"),
+    ("error.stock.not-tracked", "variation {variation} does not track stock"),
+    ("error.stock.increased", "stock was increased from {observed} to {current} after it was read; nothing was written"),
+    ("error.sale", "sale error: {error}"),
+    ("error.sale.already-running", "a sale until {until} is already recorded in the ledger. run `sale end` first"),
+    ("error.sale.not-running", "no sale is recorded in the ledger"),
+    ("error.sale.changed-externally", "{count} prices were changed after the sale started; nothing was restored. run with --skip-conflicts to leave them as they are and restore the rest"),
+    ("validation.sale-until-past", "--until ({until}) is in the past"),
+    ("error.submit", "BOOTH did not accept the change: {error}"),
+    ("error.submit.foreign-action", "the form is sent to another site ({action}); refusing to send the session"),
+    ("error.submit.rejected", "{errors}"),
+    ("error.submit.rejected-without-message", "the form was shown again without an error message"),
+    ("error.submit.signed-out", "redirected to the login page. the token may be expired."),
+    ("validation.compare-by-month", "--compare-from and --compare-to cannot be used with --by month, because the months of two periods never overlap"),
+    ("validation.artifact-path-not-found", "--artifact-path must point to existing path"),
+    ("validation.artifact-path-is-dir", "--artifact-path must point to file"),
+    ("validation.cookie-file-not-found", "--cookie-file must point to existing path"),
+    ("validation.cookie-file-is-dir", "--cookie-file must point to file"),
+    ("validation.unsupported-browser", "{browser} is not supported yet."),
+    ("validation.dir-not-found", "--dir must point to existing directory"),
+    ("validation.non-utf8-name", "`{path}` does not have valid UTF-8 name"),
+    // BOOTHの拒否理由
+    ("rejection.hint", "hint"),
+    ("rejection.empty-file", "the file is empty"),
+    ("rejection.empty-file.hint", "check that the build artifact was generated correctly."),
+    ("rejection.unsupported-format", "the file format is not supported"),
+    ("rejection.unsupported-format.hint", "archive it in a format BOOTH accepts, such as zip."),
+    ("rejection.too-large", "the file is too large"),
+    ("rejection.too-large.hint", "split the file or make it smaller."),
+    ("rejection.too-small", "the file is too small"),
+    ("rejection.too-small.hint", "check that the file is not truncated."),
+    ("rejection.unauthorized", "not logged in, or not permitted to edit this item"),
+    ("rejection.unauthorized.hint", "the token may be expired. grab it again by get-authorization-token."),
+    ("rejection.quota-exceeded", "the disk quota of the shop is exceeded"),
+    ("rejection.quota-exceeded.hint", "check usage with quota, and delete old files with --min-free and --evict-oldest."),
+    ("rejection.csrf-invalid", "the CSRF token was rejected (422)"),
+    ("rejection.csrf-invalid.hint", "try again later. if it persists, grab the token again."),
+    ("rejection.csrf-unavailable", "unable to obtain CSRF token"),
+    ("rejection.csrf-unavailable.hint", "the token may be expired. grab it again by get-authorization-token."),
+    ("rejection.not-found", "the item or the file was not found"),
+    ("rejection.not-found.hint", "check the item id, and that the item belongs to your shop."),
+    ("rejection.server-error", "BOOTH returned a server error"),
+    ("rejection.server-error.hint", "BOOTH may be under maintenance or overloaded. try again later."),
+    ("rejection.unknown", "unknown error"),
+    // 進捗
+    ("manage.getting-csrf-token", "Getting CSRF token"),
+    ("upload.url", "url: {url}"),
+    ("upload.from", "from: `{path}`"),
+    ("upload.uploaded", "uploaded as {name} ({size})"),
+    ("upload.quota", "quota: (permitted = {permitted}) - (used = {used}) = (left = {left})"),
+    ("sync.plan", "plan for item {item}:"),
+    ("sync.plan.upload", "  + upload  {name} ({size})"),
+    ("sync.plan.replace", "  ~ replace {name} ({old} -> {new})"),
+    ("sync.plan.keep", "  = keep    {name}"),
+    ("sync.plan.prune", "  - prune   {name} ({size})"),
+    ("sync.quota-left", "quota: {left} left"),
+    ("sync.confirm-prune", "{count} remote file(s) will be deleted. continue?"),
+    ("sync.uploading", "uploading {name}"),
+    ("sync.uploaded", "uploaded as {name} ({size}), {left} left"),
+    ("sync.deleting-old", "deleting old {name}"),
+    ("sync.deleting", "deleting {name}"),
+    ("sync.summary", "item {item} now has {count} downloadable(s)"),
+    ("confirm.suffix", "[y/N]"),
+    ("confirm.aborted", "aborted"),
+    ("quota.evicting", "evicting {name} ({size})"),
+    ("quota.permitted", "permitted: {size}"),
+    ("quota.used", "used:      {size}"),
+    ("quota.left", "left:      {size}"),
+    ("quota.item-usage", "item {item} uses {size} by {count} downloadable(s), oldest first:"),
+    ("item.name", "name:            {name}"),
+    ("item.state", "state:           {state}"),
+    ("item.age-restriction", "age restriction: {age_restriction}"),
+    ("item.category", "category:        {category}"),
+    ("item.tags", "tags:            {tags}"),
+    ("item.variations", "variations:"),
+    ("item.description", "description:"),
+    ("item.unchanged", "item {item} is already up to date"),
+    ("item.updated", "updated item {item}"),
+    ("variation.unchanged", "variation {variation} is already up to date"),
+    ("variation.attached", "attached {count} downloadable(s) to variation {variation}"),
+    ("variation.detached", "detached {count} downloadable(s) from variation {variation}"),
+    ("item.published", "published item {item}"),
+    ("item.unpublished", "made item {item} private"),
+    ("item.reserved", "reserved item {item} to be published at {at}"),
+    ("item.waiting", "item {item} does not support reservation. waiting until {at} to publish it"),
+    ("gallery.count", "item {item} has {count} image(s):"),
+    ("gallery.uploading", "uploading {name}"),
+    ("gallery.uploaded", "uploaded {name} as #{id}"),
+    ("gallery.deleting", "deleting {name}"),
+    ("gallery.confirm-prune", "{count} image(s) will be deleted from the gallery. continue?"),
+    ("gallery.arranged", "arranged {count} image(s) of item {item}"),
+    ("item.import.unknown-variation", "variation {variation} no longer exists and is skipped"),
+    ("item.import.missing-downloadable", "downloadable {name} ({size}) is not uploaded. upload it again"),
+    ("item.import.missing-image", "image {name} is not in the gallery. upload it again"),
+    ("item.import.confirm", "item {item} will be updated as above. continue?"),
+    ("item.create.created", "created item {item}"),
+    ("items.row", "#{id} [{state}] {name} {price} ({variations} variation(s), {downloadables} downloadable(s))"),
+    ("public.name", "{name} (#{id})"),
+    ("public.shop", "shop:         {shop} <{url}>"),
+    ("public.price", "price:        {price}"),
+    ("public.category", "category:     {category}"),
+    ("public.tags", "tags:         {tags}"),
+    ("public.wish-count", "wishes:       {count}"),
+    ("public.published-at", "published at: {at}"),
+    ("public.images", "images:       {count}"),
+    ("public.variations", "variations:"),
+    ("public.variation", "  #{id} {name}: ¥{price} ({kind}, {status}, stock: {stock})"),
+    ("verify.ok", "item {item} is public as expected"),
+    ("verify.missing-file", "downloadable {name} is missing"),
+    ("verify.not-published", "the item is {state}, not public"),
+    ("verify.not-visible", "the public page of the item is not found"),
+    ("verify.missing-variation", "variation {variation} is not on the public page"),
+    ("verify.mismatch", "{field} is {actual} on the public page, but {expected} is expected"),
+    ("verify.finding", "NG: {finding}"),
+    ("verify.file-size", "downloadable {name} is {actual} bytes, but {expected} bytes are expected"),
+    ("verify.unlinked-file", "downloadable {name} is not attached to any variation"),
+    ("verify.not-purchasable", "downloadable {name} is attached only to variations which are sold out or not public"),
+    ("orders.exported", "exported {count} record(s) from {from} to {to}"),
+    ("sales.column.key", "key"),
+    ("sales.column.quantity", "quantity"),
+    ("sales.column.revenue", "revenue"),
+    ("sales.column.fees", "fees"),
+    ("sales.column.net", "net"),
+    ("sales.column.previous-quantity", "previous quantity"),
+    ("sales.column.previous-revenue", "previous revenue"),
+    ("sales.column.previous-net", "previous net"),
+    ("sales.column.change", "revenue change"),
+    ("sales.weekday.mon", "Mon"),
+    ("sales.weekday.tue", "Tue"),
+    ("sales.weekday.wed", "Wed"),
+    ("sales.weekday.thu", "Thu"),
+    ("sales.weekday.fri", "Fri"),
+    ("sales.weekday.sat", "Sat"),
+    ("sales.weekday.sun", "Sun"),
+    ("library.row", "#{id} {name} ({shop}, {files} file(s))"),
+    ("library.row-gift", "#{id} {name} ({shop}, {files} file(s), gift)"),
+    ("library.file", "    #{id} {name}"),
+    ("library.skipped", "skipped {path} (already downloaded)"),
+    ("library.downloaded", "downloaded {path}"),
+    ("library.resumed", "resumed and downloaded {path}"),
+    ("library.failed", "failed to download {path}: {error}"),
+    ("library.download-summary", "downloaded {downloaded} file(s), skipped {skipped} file(s)"),
+    ("library.up-to-date", "no updates since the last sync"),
+    ("library.changelog-item", "#{id} {name} ({shop})"),
+    ("library.change-new", "  + {name} -> {path}"),
+    ("library.change-updated", "  ~ {name} -> {path} (updated)"),
+    ("library.change-removed", "  - {name} (removed from #{id})"),
+    ("library.extracted", "extracted {path} into {target}"),
+    ("wishlist.row", "{id} {name} ({shop}) {price}"),
+    ("watch.checking", "checking {count} item(s)"),
+    ("watch.price-down", "price down: {item} ¥{from} -> ¥{to} ({percent}% off) {url}"),
+    ("watch.price-up", "price up: {item} ¥{from} -> ¥{to} {url}"),
+    ("watch.stock", "stock: {item} {from} -> {to} {url}"),
+    ("watch.status", "status: {item} {from} -> {to} {url}"),
+    ("watch.new-variation", "new variation: {item} ¥{price} {url}"),
+    ("watch.removed-variation", "removed variation: {item} {url}"),
+    ("watch.unavailable", "no longer public: {item} {url}"),
+    ("messages.row", "#{id} {at} {buyer}: {subject}"),
+    ("messages.row-unread", "#{id} {at} {buyer}: {subject} (unread)"),
+    ("messages.subject", "#{id} {subject}"),
+    ("messages.from-shop", "[{at}] {author} (shop):"),
+    ("messages.from-buyer", "[{at}] {author}:"),
+    ("messages.replied", "replied to #{id}"),
+    ("digest.since", "since {since}"),
+    ("digest.orders", "orders: {count} (¥{revenue})"),
+    ("digest.messages", "messages: {updated} updated, {unread} of them unread"),
+    ("digest.wishes", "wishes: #{id} {name} {from} -> {to}"),
+    ("digest.low-stock", "low stock: #{id} {name} / {variation}: {stock}"),
+    ("digest.quota", "quota: {used} used of {quota} ({left} left)"),
+    ("stock.row", "#{id} {name}: {stock}"),
+    ("stock.sold-since", "{sold} sold since the stock was {observed}"),
+    ("stock.unchanged", "{name}: stock is already {stock}"),
+    ("stock.updated", "{name}: stock {from} -> {to}"),
+    ("sale.price", "#{item} {variation}: ¥{from} -> ¥{to}"),
+    ("sale.nothing", "no price to change"),
+    ("sale.confirm-start", "discount {count} items by {percent}% until {until}?"),
+    ("sale.confirm-end", "restore the prices of {count} items?"),
+    ("sale.started", "discounted {count} items by {percent}% until {until}"),
+    ("sale.ended", "restored the prices of {count} items"),
+    ("sale.not-due", "the sale lasts until {until}"),
+    ("sale.conflict", "#{item} {variation}: expected ¥{expected}, but is {current}"),
+    ("sale.conflicts-skipped", "left {count} changed prices as they are"),
+    ("sale.saved-differently", "#{item} {variation}: BOOTH saved ¥{actual} instead of ¥{expected}; recorded ¥{actual} in the ledger"),
+    ("session.localize-remote-error-deprecated", "warning: `--localize-remote-error` is deprecated and has no effect. `Accept-Language` is always sent; use `--language` to change it."),
+    ("gallery.replacing", "replacing {name}, which changed since it was uploaded"),
+    ("sale.reread-failed", "could not read item {item} back after discounting it, so the planned prices were recorded: {error}"),
+];
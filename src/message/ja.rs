@@ -0,0 +1,376 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // clap
+    ("help.about", "KisaragiEffectiveが開発した、booth.pmの利用を補助するためのツールです。\n\nこのプロジェクトはピクシブ株式会社と何ら関係を持つものではなく、ピクシブ株式会社が主導及び開発するものではありません。使用する前に<https://policies.pixiv.net/>と<https://policies.pixiv.net/#booth>を参照してください。"),
+    ("help.heading.usage", "使い方:"),
+    ("help.heading.commands", "コマンド"),
+    ("help.heading.options", "オプション"),
+    ("help.flag.help", "ヘルプを表示します"),
+    ("help.flag.version", "バージョンを表示します"),
+    ("help.language", "メッセージの言語と、BOOTHへ送る`Accept-Language`です。`ja`、`en`、`ko`、`zh-cn`、`zh-tw`が指定できます。\n\n省略した場合は`BOOTH_LANGUAGE`、`LC_ALL`、`LC_MESSAGES`、`LANG`の順に参照します。Windowsでいずれも設定されていない場合は、ユーザーの地域の設定を使います。"),
+    ("help.arg.language", "メッセージの言語と、BOOTHへ送る`Accept-Language`です。`ja`、`en`、`ko`、`zh-cn`、`zh-tw`が指定できます。"),
+    ("help.arg.booth_item_id", "アイテムのIDかURLです。例: `3519955`、<https://booth.pm/ja/items/3519955>、<https://shopname.booth.pm/items/3519955>\n\n`name:<名前>`と指定すると、名前が完全に一致するアイテムを探します。"),
+    ("help.arg.login_token", "`get-authorization-token`サブコマンドで取得できます。"),
+    ("help.arg.localize_remote_error", "非推奨: `Accept-Language`は常に送られます。変更するには`--language`を使ってください。"),
+    ("help.arg.unsafe_expose_csrf_token", "危険: X-CSRF-Tokenを標準出力に表示します。"),
+    ("help.arg.unsafe_expose_all_header", "危険: `cookie`ヘッダーを含むすべてのヘッダーを表示します。\n\nデバッグのためだけに使ってください。"),
+    ("help.arg.min_free", "アップロード後の空き容量がこの大きさを下回る場合、アップロードする前に中断します。例: `500MiB`"),
+    ("help.arg.evict_oldest", "中断する代わりに、このパターンに一致するアイテムのファイルを`--min-free`を満たすまで古い順に削除します。`*`と`?`が使えます。"),
+    ("help.help", "このメッセージか、指定したサブコマンドのヘルプを表示します"),
+    ("help.help.subcommand", "ヘルプを表示するサブコマンドです"),
+    ("help.get-authorization-token", "ブラウザのクッキーに保存されたログイントークンを表示します。"),
+    ("help.get-authorization-token.cookie_file", "firefoxなら`cookies.sqlite`、chromiumなら`Cookies`へのパスです。"),
+    ("help.get-authorization-token.browser", "`firefox`または`chromium`が指定できます。\n\nInternet Explorer、Safari、Sleipnir、Lunaspace、旧Edge及び旧Operaには対応していません。"),
+    ("help.upload", "アイテムのダウンロードファイルとしてファイルをアップロードします。"),
+    ("help.upload.artifact_path", "アップロードするファイルのパスです。"),
+    ("help.sync", "ローカルのディレクトリとアイテムのダウンロードファイルを同期します。\n\nファイルは名前、サイズ、及び前回の同期で記録したハッシュ値で比較します。"),
+    ("help.sync.dir", "同期するディレクトリです。サブディレクトリは無視します。"),
+    ("help.sync.state_file", "アップロードした内容を記録する場所です。省略した場合は`--dir`の中の`.kisaragi-booth-sync.json`です。"),
+    ("help.sync.prune", "`--dir`に存在しないファイルをBOOTHから削除します。"),
+    ("help.sync.yes", "ファイルを削除する前の確認を省略します。"),
+    ("help.sync.dry_run", "計画の表示だけを行います。"),
+    ("help.quota", "ショップの容量とアイテムのダウンロードファイルを表示します。"),
+    ("help.item", "アイテムの編集ページにある情報を読み書きします。"),
+    ("help.item.get", "アイテムの名前、説明、タグ、カテゴリー、価格、年齢制限及び公開状態を表示します。"),
+    ("help.arg.json", "JSONで表示します。"),
+    ("help.item.set", "指定した項目だけを更新します。他の項目は変更しません。"),
+    ("help.item.set.name", "アイテムの新しい名前です。"),
+    ("help.item.set.description", "アイテムの新しい説明です。"),
+    ("help.item.set.description_file", "新しい説明をこのファイルから読み込みます。"),
+    ("help.item.set.tags", "カンマ区切りのタグで、今のタグを置き換えます。空の文字列を指定するとすべて外します。"),
+    ("help.item.set.category", "アイテムのカテゴリーのIDです。"),
+    ("help.item.set.price", "円単位の価格で、`<バリエーションのIDか名前>=<価格>`の形で指定します。`<バリエーション>=`を省略するとすべてのバリエーションに適用します。複数回指定できます。"),
+    ("help.item.set.age_restriction", "`all`または`r18`が指定できます。"),
+    ("help.item.set.state", "`draft`、`private`または`public`が指定できます。"),
+    ("help.upload.variation", "アップロードしたファイルを、IDか名前で指定したこのバリエーションにも紐付けます。"),
+    ("help.variation", "アイテムのバリエーションを表示し、ダウンロードファイルを紐付けます。"),
+    ("help.variation.list", "バリエーションごとにID、名前、価格、種類と紐付いたダウンロードファイルを表示します。"),
+    ("help.variation.attach", "ダウンロードファイルを、バリエーションを購入した人がダウンロードできるようにします。"),
+    ("help.variation.detach", "ダウンロードファイルを、バリエーションを購入した人がダウンロードできないようにします。ファイルは削除しません。"),
+    ("help.arg.variation", "バリエーションのIDか名前です。"),
+    ("help.arg.files", "ダウンロードファイルのIDか名前です。複数回指定できます。"),
+    ("help.item.publish", "アイテムを公開します。"),
+    ("help.item.publish.at", "すぐに公開する代わりに、この日時に公開します。例: `2026-01-01T00:00:00+09:00`\n\nアイテムがBOOTHの公開予約に対応していればそれを使い、そうでなければその日時まで待ちます。"),
+    ("help.item.unpublish", "アイテムを非公開にします。公開予約があれば取り消します。"),
+    ("help.gallery", "アイテムのギャラリーの画像を管理します。"),
+    ("help.gallery.list", "ギャラリーの画像を表示順に表示します。"),
+    ("help.gallery.upload", "ギャラリーの末尾に画像を追加します。アップロードする前にすべての画像を検証します。"),
+    ("help.gallery.upload.images", "JPEG、PNGまたはGIFの画像のパスです。複数回指定できます。"),
+    ("help.gallery.delete", "ギャラリーから画像を削除します。"),
+    ("help.gallery.delete.images", "画像のIDか名前です。複数回指定できます。"),
+    ("help.gallery.arrange", "ギャラリーを指定した画像の順に並べます。まだギャラリーにない画像はアップロードします。\n\n画像はファイル名で照合します。名前が一致しても中身が違えば、手元の画像を同じ位置にアップロードし、古い画像を削除します。"),
+    ("help.gallery.arrange.images", "JPEG、PNGまたはGIFの画像のパスを表示順に指定します。複数回指定できます。"),
+    ("help.gallery.arrange.prune", "指定しなかった画像を削除します。指定しなければ、それらは指定した画像の後ろに並べます。"),
+    ("help.gallery.arrange.yes", "画像を削除する前の確認を省略します。"),
+    ("help.item.export", "アイテムの編集できる内容をすべてJSONで表示します。`item import`で書き戻せます。"),
+    ("help.item.import", "`item export`で書き出したファイルをアイテムに書き戻します。書き込む前に変更を表示します。\n\n画像とダウンロードファイルは中身を書き出していないので、並べ替えと確認だけを行います。"),
+    ("help.item.import.file", "`item export`で書き出したファイルです。"),
+    ("help.item.import.yes", "書き込む前の確認を省略します。"),
+    ("help.item.import.dry_run", "変更の表示だけを行います。"),
+    ("help.item.create", "下書きのアイテムを新しく作り、そのIDを表示します。"),
+    ("help.item.create.from_template", "`item export`で書き出したファイルです。"),
+    ("help.item.create.clone", "複製する既存のアイテムのIDです。"),
+    ("help.item.create.downloadables_dir", "テンプレートのダウンロードファイルがあるディレクトリです。ファイルは名前で照合します。"),
+    ("help.items", "ショップのアイテムを一覧します。"),
+    ("help.items.list", "すべてのアイテムのID、名前、公開状態、価格帯、バリエーションの数及びダウンロードファイルの数を表示します。"),
+    ("help.items.list.status", "この公開状態のアイテムだけを表示します。`draft`、`private`または`public`が指定できます。"),
+    ("help.items.list.name_match", "名前がこのパターンに一致するアイテムだけを表示します。`*`と`?`が使えます。"),
+    ("help.public", "購入者がbooth.pmで見る情報を読みます。ログインは必要ありません。"),
+    ("help.public.item", "アイテムの名前、価格、バリエーション、在庫、ショップ、画像、タグ、スキの数及び公開日を表示します。\n\n接続先は`BOOTH_PUBLIC_BASE_URL`で変更できます。"),
+    ("help.public.item.item", "アイテムのIDかURLです。"),
+    ("help.verify", "アイテムが公開されていて期待するダウンロードファイルがあること、及び公開ページが管理画面と一致することを確認します。\n\n食い違いがあれば失敗として終了します。"),
+    ("help.verify.expect_files", "アップロードされていなければならないファイルの名前、または同じ名前と大きさでアップロードされていなければならないローカルのファイルです。名前だけの場合は、マニフェストに大きさが書かれているときだけ大きさを確認します。複数回指定できます。"),
+    ("help.verify.manifest", "期待する名前、価格及びダウンロードファイルを記した、`item export`で書き出したファイルです。"),
+    ("help.orders", "ショップの注文を読みます。"),
+    ("help.orders.export", "期間内の注文を、注文された商品ごとに古い順で標準出力に書き出します。日付は日本時間です。"),
+    ("help.arg.from", "期間の最初の日です。例: `2026-09-01`"),
+    ("help.arg.to", "期間の最後の日です。この日も含みます。例: `2026-09-30`"),
+    ("help.orders.export.format", "`csv`または`json`が指定できます。"),
+    ("help.sales", "`orders export`で書き出した注文を、ネットワークに接続せずに集計します。"),
+    ("help.sales.report", "アイテム、バリエーション、月または曜日ごとに、数量、売上、手数料及び受取額を表示します。\n\nキャンセルされた注文は除き、手数料は注文ごとに5.6% + 22円と見積もります。"),
+    ("help.sales.report.orders", "`orders export`で書き出したファイルです。拡張子が`.json`ならJSON、それ以外はCSVとして読みます。"),
+    ("help.sales.report.by", "`item`、`variation`、`month`または`weekday`が指定できます。"),
+    ("help.sales.report.compare_from", "比較する期間の最初の日です。`--by month`とは併用できません。"),
+    ("help.sales.report.compare_to", "比較する期間の最後の日です。この日も含みます。"),
+    ("help.sales.report.format", "`table`または`csv`が指定できます。"),
+    ("help.library", "購入したアイテムとギフトとして受け取ったアイテムを一覧し、ダウンロードします。"),
+    ("help.library.list", "購入したアイテムとギフトとして受け取ったアイテムを、ダウンロードファイルとともに一覧します。"),
+    ("help.library.download", "すべてのダウンロードファイルを`--dir`の下に保存し、アイテムの情報と購入日を隣の`booth.json` (レイアウトで複数のアイテムのファイルが同じディレクトリに入る場合は`booth-<アイテムのID>.json`) に書き出します。\n\n同じ大きさのファイルがすでにあれば飛ばし、中断したダウンロードは続きから再開します。ダウンロードできなかったファイルがあっても残りを続け、最後に失敗として終了します。"),
+    ("help.library.download.dir", "ファイルを保存するディレクトリです。"),
+    ("help.library.sync", "前回から増えたか変わったファイルだけをダウンロードし、更新されたアイテムを表示します。\n\nライブラリから消えたファイルは、保存したものを残します。"),
+    ("help.library.sync.dir", "ファイルを保存するディレクトリです。"),
+    ("help.library.sync.index", "ダウンロードしたファイルを覚えておくSQLiteのデータベースです。既定では`--dir`の中の`.booth-library.sqlite3`です。"),
+    ("help.arg.layout", "`--dir`の下のどこに保存するかです。例: `{shop}/{item_name} ({item_id})/{file_name}`\n\n`{shop}`、`{item_name}`、`{item_id}`、`{file_name}`及び`{file_id}`が使えます。"),
+    ("help.arg.extract", "ダウンロードしたzipファイルを、隣の同じ名前のフォルダーに展開します。"),
+    ("help.wishlist", "ほしいものリストを読みます。"),
+    ("help.wishlist.list", "ほしいものリストのアイテムを一覧します。各行はアイテムのIDで始まるので、そのまま`watch prices --items-file -`に渡せます。"),
+    ("help.watch", "実行するたびに公開されている情報を記録し、前回からの変化を報告します。"),
+    ("help.watch.prices", "バリエーションの価格、在庫及び状態を記録し、値下げ、値上げ、在庫と状態の変化を一件一行で表示します。\n\n変化がなければ何も表示しません。ログインは必要ありません。"),
+    ("help.watch.prices.items", "見張るアイテムのIDかURLです。"),
+    ("help.watch.prices.items_file", "各行の先頭にアイテムのIDかURLを書いたファイルです。`-`なら標準入力から読みます。"),
+    ("help.watch.prices.db", "これまでに見た価格を記録するデータベースファイル (sqlite3) です。"),
+    ("help.watch.prices.json", "変化を一件ずつ一行のJSONで表示します。"),
+    ("help.messages", "購入者からのメッセージを読み、返信します。"),
+    ("help.messages.list", "メッセージのやりとりを新しい順に一覧します。"),
+    ("help.messages.list.unread", "未読のやりとりだけを一覧します。"),
+    ("help.messages.show", "やりとりのすべてのメッセージを古い順に表示します。"),
+    ("help.arg.thread", "`messages list`で表示されるやりとりのIDです。"),
+    ("help.messages.reply", "やりとりに返信します。"),
+    ("help.messages.reply.body_file", "返信の本文を書いたテキストファイルです。`-`なら標準入力から読みます。"),
+    ("help.digest", "前回の実行からの新しい注文、メッセージ、スキの数、在庫の少ないバリエーションと容量をまとめて表示します。\n\n初めての実行では、直前の24時間を対象にします。BOOTHにはレビューがないため、反応はスキの数だけを表示します。スキの数は公開ページから読むので、非公開や下書きのアイテムでは表示しません。それらの在庫は編集ページから読みます。"),
+    ("help.digest.state_file", "前回の実行を記録するファイルです。既定では現在のディレクトリの`.kisaragi-booth-digest.json`です。"),
+    ("help.digest.low_stock", "在庫がこの数以下のバリエーションを報告します。"),
+    ("help.stock", "バリエーションの在庫を読み書きします。"),
+    ("help.stock.get", "バリエーションごとの在庫を表示します。`-`は在庫を持たないバリエーションです。"),
+    ("help.stock.set", "バリエーションの在庫を設定します。"),
+    ("help.stock.set.stock", "新しい在庫の数です。"),
+    ("help.stock.set.decrement_on", "新しい在庫を決める前にスクリプトが読んだ在庫の数です。それから売れた分を差し引き、その間に在庫が増えていれば何も書き込みません。"),
+    ("help.sale", "元の価格を手元の台帳に控えて、アイテムのセールを行います。"),
+    ("help.sale.start", "アイテムのすべてのバリエーションを値下げし、元の価格を台帳に控えます。"),
+    ("help.sale.start.percent", "割引率です。1から99のパーセントで指定します。1円未満は切り捨て、100円を下回ることはありません。"),
+    ("help.sale.start.items", "値下げするアイテムをカンマ区切りで指定します。`all`は公開されているすべてのアイテムです。"),
+    ("help.sale.start.until", "セールの最終日です。例: `2026-11-03`。この日を過ぎると`sale end --if-due`が価格を戻します。"),
+    ("help.sale.start.ledger", "元の価格を控えるファイルです。省略すると現在のディレクトリの`.kisaragi-booth-sale.json`です。"),
+    ("help.sale.start.yes", "書き込む前の確認を省略します。"),
+    ("help.sale.start.dry_run", "計画を表示するだけにします。"),
+    ("help.sale.end", "台帳に控えた元の価格に戻します。セールを始めた後に変わった価格が一つでもあれば、`--skip-conflicts`を指定しない限り何も書き込みません。"),
+    ("help.sale.end.ledger", "元の価格を控えたファイルです。省略すると現在のディレクトリの`.kisaragi-booth-sale.json`です。"),
+    ("help.sale.end.if_due", "`--until`の日を過ぎるまでは何もしません。定期的に実行するジョブ向けです。"),
+    ("help.sale.end.skip_conflicts", "セールを始めた後に変わった価格はそのままにして台帳から外し、残りを戻します。"),
+    ("help.sale.end.yes", "書き込む前の確認を省略します。"),
+    ("help.sale.end.dry_run", "計画を表示するだけにします。"),
+    // ExecutionError
+    ("error.database", "データベースのエラーが発生しました: {error}"),
+    ("error.argument", "コマンドライン引数の使い方が正しくありません: {message}"),
+    ("error.io", "入出力のエラーが発生しました: {error}"),
+    ("error.get-authorization-token", "トークンの取得中にエラーが発生しました: {error}"),
+    ("error.http", "HTTPリクエストのエラーが発生しました: {error}"),
+    ("error.booth", "BOOTHのサーバーがエラーを返しました: {error}"),
+    ("error.json", "JSONのエラーが発生しました: {error}"),
+    ("error.quota-exceeded", "BOOTHの空き容量が足りません: 残りは{left}ですが、{required}必要です"),
+    ("error.token-not-found", "トークンが見つかりません"),
+    ("error.multiple-tokens", "トークンが複数 ({count}個) 見つかりました"),
+    ("error.unsupported-language", "対応していない言語です: {language} (ja、en、ko、zh-cn、zh-twのいずれかを指定してください)"),
+    ("error.size.empty", "大きさが空です"),
+    ("error.size.invalid-number", "数値が正しくありません: {number}"),
+    ("error.size.unknown-unit", "単位が正しくありません: {unit} (B、KiB、MiB、GiBのいずれかを指定してください)"),
+    ("error.size.too-large", "大きさは100GiB以下にしてください"),
+    ("error.item-form", "アイテムの編集フォームを読み取れませんでした: {error}"),
+    ("error.item-form.not-found", "編集フォームが見つかりません。トークンの期限が切れている可能性があります。"),
+    ("error.item-form.missing-field", "`{field}`がありません"),
+    ("error.item-form.invalid-value", "`{field}`の値が正しくありません: {value}"),
+    ("error.item-form.unknown-variation", "そのようなバリエーションはありません: {variation}"),
+    ("error.item.invalid-price", "価格が正しくありません: {price} (`<バリエーション>=<円>`または`<円>`を指定してください)"),
+    ("validation.unknown-downloadable", "そのようなダウンロードファイルはありません: {file}"),
+    ("error.item.invalid-time", "日時が正しくありません: {time} (`2026-01-01T00:00:00+09:00`のようなRFC 3339の形式で指定してください): {error}"),
+    ("error.gallery", "画像が正しくありません: {error}"),
+    ("error.gallery.unsupported-format", "`{path}`はJPEG、PNG及びGIFの画像ではありません"),
+    ("error.gallery.too-large", "`{path}`が大きすぎます: {size} (上限は{max}です)"),
+    ("error.gallery.dimensions", "`{path}`は{width}x{height}ですが、縦横はそれぞれ{min}から{max}ピクセルにしてください"),
+    ("error.gallery.duplicate-name", "{name}という名前の画像が複数あります"),
+    ("validation.unknown-image", "そのような画像はありません: {image}"),
+    ("error.item-form.unexpected-redirect", "作成したアイテムのIDが{url}から見つかりません"),
+    ("error.item-ref.invalid-id", "アイテムのIDが正しくありません: {id} (正の数を指定してください)"),
+    ("error.item-ref.unsupported-url", "BOOTHのアイテムのURLではありません: {url}"),
+    ("error.item-not-found", "{name}という名前のアイテムはありません"),
+    ("error.layout-collision", "複数のファイルの保存先が{path}になります。--layoutに{item_id}か{file_id}を加えてください"),
+    ("error.download-failed", "{count}個のファイルをダウンロードできませんでした"),
+    ("error.ambiguous-item", "{name}という名前のアイテムが{count}個あります。IDで指定してください"),
+    ("validation.public-item-by-name", "publicでは`name:`は使えません。IDかURLで指定してください"),
+    ("error.not-public", "アイテム{item}は公開されていません"),
+    ("error.verification-failed", "アイテム{item}の確認で{count}個の問題が見つかりました"),
+    ("error.csv", "CSVのエラーが発生しました: {error}"),
+    ("validation.period-reversed", "--from ({from}) は--to ({to}) より後にしないでください"),
+    ("error.zip", "zipのエラーが発生しました: {error}"),
+    ("error.layout.unknown-placeholder", "レイアウトに不明なプレースホルダー`{{placeholder}}`があります"),
+    ("error.layout.unclosed-brace", "レイアウトに閉じられていない波括弧があります"),
+    ("error.layout.empty-segment", "レイアウトに空、`.`または`..`のディレクトリを含めることはできません"),
+    ("error.layout.no-file-name", "レイアウトの最後の部分には`{file_name}`か`{file_id}`が必要です"),
+    ("validation.watch-item-by-name", "watchでは`name:`は使えません。IDかURLで指定してください"),
+    ("validation.empty-message-body", "返信の本文が空です"),
+    ("error.stock", "在庫のエラーが発生しました: {error}"),
+    ("error.stock.not-tracked", "バリエーション{variation}は在庫を持っていません"),
+    ("error.stock.increased", "読んだ後に在庫が{observed}から{current}に増えたため、何も書き込みませんでした"),
+    ("error.sale", "セールのエラーが発生しました: {error}"),
+    ("error.sale.already-running", "{until}までのセールが既に台帳にあります。先に`sale end`を実行してください"),
+    ("error.sale.not-running", "台帳にセールが記録されていません"),
+    ("error.sale.changed-externally", "セールを始めた後に{count}件の価格が変わったため、何も戻しませんでした。それらをそのままにして残りを戻すには--skip-conflictsを指定してください"),
+    ("validation.sale-until-past", "--until ({until})は過去の日付です"),
+    ("error.submit", "BOOTHが変更を受け付けませんでした: {error}"),
+    ("error.submit.foreign-action", "フォームの送信先が別のサイト ({action}) のため、セッションを送りませんでした"),
+    ("error.submit.rejected", "{errors}"),
+    ("error.submit.rejected-without-message", "エラーメッセージなしでフォームがもう一度表示されました"),
+    ("error.submit.signed-out", "ログインページへ移動しました。トークンの期限が切れている可能性があります。"),
+    ("validation.compare-by-month", "--by monthでは二つの期間の月が重ならないため、--compare-fromと--compare-toは使えません"),
+    ("validation.artifact-path-not-found", "--artifact-pathには存在するパスを指定してください"),
+    ("validation.artifact-path-is-dir", "--artifact-pathにはファイルを指定してください"),
+    ("validation.cookie-file-not-found", "--cookie-fileには存在するパスを指定してください"),
+    ("validation.cookie-file-is-dir", "--cookie-fileにはファイルを指定してください"),
+    ("validation.unsupported-browser", "{browser}にはまだ対応していません。"),
+    ("validation.dir-not-found", "--dirには存在するディレクトリを指定してください"),
+    ("validation.non-utf8-name", "`{path}`の名前が正しいUTF-8ではありません"),
+    // BOOTHの拒否理由
+    ("rejection.hint", "ヒント"),
+    ("rejection.empty-file", "ファイルが空です"),
+    ("rejection.empty-file.hint", "ビルドの成果物が正しく生成されているか確認してください。"),
+    ("rejection.unsupported-format", "対応していないファイル形式です"),
+    ("rejection.unsupported-format.hint", "zipなどBOOTHが受け付ける形式に圧縮してください。"),
+    ("rejection.too-large", "ファイルが大きすぎます"),
+    ("rejection.too-large.hint", "ファイルを分割するか、小さくしてください。"),
+    ("rejection.too-small", "ファイルが小さすぎます"),
+    ("rejection.too-small.hint", "ファイルの中身が欠けていないか確認してください。"),
+    ("rejection.unauthorized", "ログインしていないか、このアイテムを編集する権限がありません"),
+    ("rejection.unauthorized.hint", "トークンの期限が切れている可能性があります。get-authorization-tokenで取得し直してください。"),
+    ("rejection.quota-exceeded", "ショップの容量が足りません"),
+    ("rejection.quota-exceeded.hint", "quotaで容量を確認し、--min-freeと--evict-oldestで古いファイルを削除してください。"),
+    ("rejection.csrf-invalid", "CSRFトークンが受け付けられませんでした (422)"),
+    ("rejection.csrf-invalid.hint", "時間をおいてもう一度実行してください。続く場合はトークンを取得し直してください。"),
+    ("rejection.csrf-unavailable", "CSRFトークンを取得できませんでした"),
+    ("rejection.csrf-unavailable.hint", "トークンの期限が切れている可能性があります。get-authorization-tokenで取得し直してください。"),
+    ("rejection.not-found", "アイテムかファイルが見つかりません"),
+    ("rejection.not-found.hint", "アイテムのIDと、自分のショップのアイテムであることを確認してください。"),
+    ("rejection.server-error", "BOOTHのサーバーでエラーが発生しました"),
+    ("rejection.server-error.hint", "メンテナンス中か混雑している可能性があります。時間をおいてもう一度実行してください。"),
+    ("rejection.unknown", "不明なエラーです"),
+    // 進捗
+    ("manage.getting-csrf-token", "CSRFトークンを取得しています"),
+    ("upload.url", "URL: {url}"),
+    ("upload.from", "ファイル: `{path}`"),
+    ("upload.uploaded", "{name} ({size}) としてアップロードしました"),
+    ("upload.quota", "容量: (上限 = {permitted}) - (使用済み = {used}) = (残り = {left})"),
+    ("sync.plan", "アイテム{item}の計画:"),
+    ("sync.plan.upload", "  + 追加 {name} ({size})"),
+    ("sync.plan.replace", "  ~ 更新 {name} ({old} -> {new})"),
+    ("sync.plan.keep", "  = 維持 {name}"),
+    ("sync.plan.prune", "  - 削除 {name} ({size})"),
+    ("sync.quota-left", "容量: 残り{left}"),
+    ("sync.confirm-prune", "BOOTHから{count}個のファイルを削除します。続けますか？"),
+    ("sync.uploading", "{name}をアップロードしています"),
+    ("sync.uploaded", "{name} ({size}) としてアップロードしました。残り{left}"),
+    ("sync.deleting-old", "古い{name}を削除しています"),
+    ("sync.deleting", "{name}を削除しています"),
+    ("sync.summary", "アイテム{item}のダウンロードファイルは{count}個になりました"),
+    ("confirm.suffix", "[y/N]"),
+    ("confirm.aborted", "中断しました"),
+    ("quota.evicting", "{name} ({size}) を削除しています"),
+    ("quota.permitted", "上限:     {size}"),
+    ("quota.used", "使用済み: {size}"),
+    ("quota.left", "残り:     {size}"),
+    ("quota.item-usage", "アイテム{item}は{count}個のファイルで{size}を使っています (古い順):"),
+    ("item.name", "名前:       {name}"),
+    ("item.state", "公開状態:   {state}"),
+    ("item.age-restriction", "年齢制限:   {age_restriction}"),
+    ("item.category", "カテゴリー: {category}"),
+    ("item.tags", "タグ:       {tags}"),
+    ("item.variations", "バリエーション:"),
+    ("item.description", "説明:"),
+    ("item.unchanged", "アイテム{item}は既に最新です"),
+    ("item.updated", "アイテム{item}を更新しました"),
+    ("variation.unchanged", "バリエーション{variation}は既に最新です"),
+    ("variation.attached", "{count}個のダウンロードファイルをバリエーション{variation}に紐付けました"),
+    ("variation.detached", "{count}個のダウンロードファイルをバリエーション{variation}から外しました"),
+    ("item.published", "アイテム{item}を公開しました"),
+    ("item.unpublished", "アイテム{item}を非公開にしました"),
+    ("item.reserved", "アイテム{item}を{at}に公開するよう予約しました"),
+    ("item.waiting", "アイテム{item}は公開予約に対応していません。公開する{at}まで待ちます"),
+    ("gallery.count", "アイテム{item}には{count}枚の画像があります:"),
+    ("gallery.uploading", "{name}をアップロードしています"),
+    ("gallery.uploaded", "{name}を#{id}としてアップロードしました"),
+    ("gallery.deleting", "{name}を削除しています"),
+    ("gallery.confirm-prune", "ギャラリーから{count}枚の画像を削除します。続けますか？"),
+    ("gallery.arranged", "アイテム{item}の{count}枚の画像を並べ替えました"),
+    ("item.import.unknown-variation", "バリエーション{variation}はもう存在しないので飛ばします"),
+    ("item.import.missing-downloadable", "ダウンロードファイル{name} ({size}) がアップロードされていません。アップロードし直してください"),
+    ("item.import.missing-image", "画像{name}がギャラリーにありません。アップロードし直してください"),
+    ("item.import.confirm", "アイテム{item}を上のように更新します。続けますか？"),
+    ("item.create.created", "アイテム{item}を作成しました"),
+    ("items.row", "#{id} [{state}] {name} {price} (バリエーション{variations}個、ダウンロードファイル{downloadables}個)"),
+    ("public.name", "{name} (#{id})"),
+    ("public.shop", "ショップ: {shop} <{url}>"),
+    ("public.price", "価格:     {price}"),
+    ("public.category", "カテゴリー: {category}"),
+    ("public.tags", "タグ:     {tags}"),
+    ("public.wish-count", "スキ:     {count}"),
+    ("public.published-at", "公開日:   {at}"),
+    ("public.images", "画像:     {count}枚"),
+    ("public.variations", "バリエーション:"),
+    ("public.variation", "  #{id} {name}: ¥{price} ({kind}、{status}、在庫: {stock})"),
+    ("verify.ok", "アイテム{item}は期待どおりに公開されています"),
+    ("verify.missing-file", "ダウンロードファイル{name}がありません"),
+    ("verify.not-published", "アイテムは公開されていません ({state})"),
+    ("verify.not-visible", "アイテムの公開ページが見つかりません"),
+    ("verify.missing-variation", "バリエーション{variation}が公開ページにありません"),
+    ("verify.mismatch", "公開ページの{field}は{actual}ですが、{expected}のはずです"),
+    ("verify.finding", "NG: {finding}"),
+    ("verify.file-size", "ダウンロードファイル{name}は{actual}バイトですが、{expected}バイトのはずです"),
+    ("verify.unlinked-file", "ダウンロードファイル{name}がどのバリエーションにも紐付いていません"),
+    ("verify.not-purchasable", "ダウンロードファイル{name}は売り切れか非公開のバリエーションにしか紐付いていません"),
+    ("orders.exported", "{from}から{to}までの{count}件を書き出しました"),
+    ("sales.column.key", "区分"),
+    ("sales.column.quantity", "数量"),
+    ("sales.column.revenue", "売上"),
+    ("sales.column.fees", "手数料"),
+    ("sales.column.net", "受取額"),
+    ("sales.column.previous-quantity", "比較期間の数量"),
+    ("sales.column.previous-revenue", "比較期間の売上"),
+    ("sales.column.previous-net", "比較期間の受取額"),
+    ("sales.column.change", "売上の増減"),
+    ("sales.weekday.mon", "月"),
+    ("sales.weekday.tue", "火"),
+    ("sales.weekday.wed", "水"),
+    ("sales.weekday.thu", "木"),
+    ("sales.weekday.fri", "金"),
+    ("sales.weekday.sat", "土"),
+    ("sales.weekday.sun", "日"),
+    ("library.row", "#{id} {name} ({shop}、ファイル{files}個)"),
+    ("library.row-gift", "#{id} {name} ({shop}、ファイル{files}個、ギフト)"),
+    ("library.file", "    #{id} {name}"),
+    ("library.skipped", "{path}は既にダウンロード済みのため飛ばしました"),
+    ("library.downloaded", "{path}をダウンロードしました"),
+    ("library.resumed", "{path}のダウンロードを再開して完了しました"),
+    ("library.failed", "{path}をダウンロードできませんでした: {error}"),
+    ("library.download-summary", "{downloaded}個のファイルをダウンロードし、{skipped}個を飛ばしました"),
+    ("library.up-to-date", "前回の同期から更新はありません"),
+    ("library.changelog-item", "#{id} {name} ({shop})"),
+    ("library.change-new", "  + {name} -> {path}"),
+    ("library.change-updated", "  ~ {name} -> {path} (更新)"),
+    ("library.change-removed", "  - {name} (#{id}から削除)"),
+    ("library.extracted", "{path}を{target}に展開しました"),
+    ("wishlist.row", "{id} {name} ({shop}) {price}"),
+    ("watch.checking", "{count}個のアイテムを確認しています"),
+    ("watch.price-down", "値下げ: {item} ¥{from} -> ¥{to} ({percent}%引き) {url}"),
+    ("watch.price-up", "値上げ: {item} ¥{from} -> ¥{to} {url}"),
+    ("watch.stock", "在庫: {item} {from} -> {to} {url}"),
+    ("watch.status", "状態: {item} {from} -> {to} {url}"),
+    ("watch.new-variation", "新しいバリエーション: {item} ¥{price} {url}"),
+    ("watch.removed-variation", "なくなったバリエーション: {item} {url}"),
+    ("watch.unavailable", "公開されなくなりました: {item} {url}"),
+    ("messages.row", "#{id} {at} {buyer}: {subject}"),
+    ("messages.row-unread", "#{id} {at} {buyer}: {subject} (未読)"),
+    ("messages.subject", "#{id} {subject}"),
+    ("messages.from-shop", "[{at}] {author} (ショップ):"),
+    ("messages.from-buyer", "[{at}] {author}:"),
+    ("messages.replied", "#{id}に返信しました"),
+    ("digest.since", "{since}以降"),
+    ("digest.orders", "注文: {count}件 (¥{revenue})"),
+    ("digest.messages", "メッセージ: 更新{updated}件 (うち未読{unread}件)"),
+    ("digest.wishes", "スキ: #{id} {name} {from} -> {to}"),
+    ("digest.low-stock", "在庫僅少: #{id} {name} / {variation}: {stock}"),
+    ("digest.quota", "容量: {quota}のうち{used}使用 (残り{left})"),
+    ("stock.row", "#{id} {name}: {stock}"),
+    ("stock.sold-since", "在庫が{observed}だったときから{sold}個売れました"),
+    ("stock.unchanged", "{name}: 在庫は既に{stock}です"),
+    ("stock.updated", "{name}: 在庫 {from} -> {to}"),
+    ("sale.price", "#{item} {variation}: ¥{from} -> ¥{to}"),
+    ("sale.nothing", "変更する価格はありません"),
+    ("sale.confirm-start", "{count}件のアイテムを{until}まで{percent}%値下げしますか？"),
+    ("sale.confirm-end", "{count}件のアイテムの価格を戻しますか？"),
+    ("sale.started", "{count}件のアイテムを{until}まで{percent}%値下げしました"),
+    ("sale.ended", "{count}件のアイテムの価格を戻しました"),
+    ("sale.not-due", "セールは{until}までです"),
+    ("sale.conflict", "#{item} {variation}: ¥{expected}のはずが{current}になっています"),
+    ("sale.conflicts-skipped", "変わっていた{count}件の価格はそのままにしました"),
+    ("sale.saved-differently", "#{item} {variation}: ¥{expected}ではなく¥{actual}で保存されたため、台帳には¥{actual}を控えました"),
+    ("session.localize-remote-error-deprecated", "警告: `--localize-remote-error`は非推奨で、何の効果もありません。`Accept-Language`は常に送られます。変更するには`--language`を使ってください。"),
+    ("gallery.replacing", "アップロードした後に変更された{name}を入れ替えています"),
+    ("sale.reread-failed", "値下げした後にアイテム{item}を読み直せなかったため、予定した価格を控えました: {error}"),
+];
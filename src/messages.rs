@@ -0,0 +1,103 @@
+use std::io::Read;
+use std::path::Path;
+use crate::booth::{MessageThread, JST};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+
+fn filter(threads: Vec<MessageThread>, unread: bool) -> Vec<MessageThread> {
+    threads.into_iter().filter(|x| !unread || x.unread).collect()
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn list(client: &ManageClient, unread: bool, json: bool) -> Result<(), ExecutionError> {
+    let threads = filter(client.message_threads().await?, unread);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&threads)?);
+        return Ok(())
+    }
+
+    for thread in &threads {
+        let at = thread.updated_at.with_timezone(&JST).format("%Y-%m-%d %H:%M");
+        if thread.unread {
+            println!("{}", message!("messages.row-unread", id = thread.id, at = at, buyer = thread.buyer, subject = thread.subject));
+        } else {
+            println!("{}", message!("messages.row", id = thread.id, at = at, buyer = thread.buyer, subject = thread.subject));
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn show(client: &ManageClient, thread_id: u64, json: bool) -> Result<(), ExecutionError> {
+    let thread = client.message_thread(thread_id).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&thread)?);
+        return Ok(())
+    }
+
+    println!("{}", message!("messages.subject", id = thread.id, subject = thread.subject));
+    for message in &thread.messages {
+        let at = message.sent_at.with_timezone(&JST).format("%Y-%m-%d %H:%M");
+        println!();
+        if message.from_shop {
+            println!("{}", message!("messages.from-shop", at = at, author = message.author));
+        } else {
+            println!("{}", message!("messages.from-buyer", at = at, author = message.author));
+        }
+        println!("{}", message.body);
+    }
+
+    Ok(())
+}
+
+/// `-`なら標準入力から読む
+fn read_body(path: &Path) -> Result<String, ExecutionError> {
+    let body = if path == Path::new("-") {
+        let mut body = String::new();
+        std::io::stdin().read_to_string(&mut body)?;
+        body
+    } else {
+        std::fs::read_to_string(path)?
+    };
+
+    if body.trim().is_empty() {
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.empty-message-body")))
+    }
+
+    Ok(body.trim_end().to_string())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn reply(client: &ManageClient, thread_id: u64, body_file: &Path) -> Result<(), ExecutionError> {
+    let body = read_body(body_file)?;
+    client.reply_message(thread_id, &body).await?;
+    println!("{}", message!("messages.replied", id = thread_id));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use crate::booth::MessageThread;
+    use crate::messages::filter;
+
+    #[test]
+    fn unread_only() {
+        let thread = |id: u64, unread: bool| MessageThread {
+            id,
+            subject: String::new(),
+            buyer: String::new(),
+            unread,
+            updated_at: DateTime::parse_from_rfc3339("2026-10-01T09:00:00+09:00").unwrap(),
+        };
+
+        let threads = vec![thread(1, true), thread(2, false), thread(3, true)];
+        assert_eq!(filter(threads.clone(), true).iter().map(|x| x.id).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(filter(threads, false).len(), 3);
+    }
+}
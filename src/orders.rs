@@ -0,0 +1,91 @@
+use chrono::{DateTime, Days, FixedOffset, NaiveDate};
+use strum::EnumString;
+use crate::booth::{OrderRecord, JST};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+
+#[derive(EnumString, Copy, Clone, Eq, PartialEq, Debug)]
+#[strum(serialize_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// `--from`と`--to`の日付を両端を含む日本時間の範囲にする。終わりは翌日の0時を含まない。
+fn period(from: NaiveDate, to: NaiveDate) -> Result<(DateTime<FixedOffset>, DateTime<FixedOffset>), ExecutionError> {
+    if from > to {
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.period-reversed", from = from, to = to)))
+    }
+
+    let start_of = |date: NaiveDate| date.and_hms_opt(0, 0, 0).expect("midnight exists").and_local_timezone(JST).single().expect("JST has no transition");
+    let end = to.checked_add_days(Days::new(1)).expect("not the end of time");
+
+    Ok((start_of(from), start_of(end)))
+}
+
+fn write(records: &[OrderRecord], format: ExportFormat, out: impl std::io::Write) -> Result<(), ExecutionError> {
+    match format {
+        ExportFormat::Json => serde_json::to_writer_pretty(out, records)?,
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 期間内の注文を古い順に標準出力に書き出す。日時は日本時間にそろえる。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn export(client: &ManageClient, from: NaiveDate, to: NaiveDate, format: ExportFormat) -> Result<(), ExecutionError> {
+    let (start, end) = period(from, to)?;
+    let mut records = client.orders_since(start).await?
+        .into_iter()
+        .filter(|x| x.ordered_at < end)
+        .map(|x| OrderRecord { ordered_at: x.ordered_at.with_timezone(&JST), ..x })
+        .collect::<Vec<_>>();
+    records.sort_by_key(|x| (x.ordered_at, x.order_id));
+
+    write(&records, format, std::io::stdout().lock())?;
+    eprintln!("{}", message!("orders.exported", count = records.len(), from = from, to = to));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, NaiveDate};
+    use crate::booth::{ItemId, OrderRecord};
+    use crate::orders::{period, write, ExportFormat};
+
+    #[test]
+    fn jst_period() {
+        let date = |x: &str| x.parse::<NaiveDate>().unwrap();
+        let (start, end) = period(date("2026-09-01"), date("2026-09-30")).unwrap();
+        assert_eq!(start, DateTime::parse_from_rfc3339("2026-08-31T15:00:00Z").unwrap());
+        assert_eq!(end.to_rfc3339(), "2026-10-01T00:00:00+09:00");
+        assert!(period(date("2026-09-02"), date("2026-09-01")).is_err());
+    }
+
+    #[test]
+    fn csv() {
+        let record = OrderRecord {
+            order_id: 1001,
+            ordered_at: DateTime::parse_from_rfc3339("2026-09-01T00:10:00+09:00").unwrap(),
+            item_id: ItemId::new(3_519_955).unwrap(),
+            item_name: "Sample, \"quoted\"".to_string(),
+            variation: "Basic".to_string(),
+            price: 500,
+            payment_method: "paypal".to_string(),
+            status: "completed".to_string(),
+        };
+
+        let mut out = vec![];
+        write(&[record], ExportFormat::Csv, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "order_id,ordered_at,item_id,item_name,variation,price,payment_method,status\n1001,2026-09-01T00:10:00+09:00,3519955,\"Sample, \"\"quoted\"\"\",Basic,500,paypal,completed\n");
+    }
+}
@@ -1,4 +1,8 @@
 use core::num::NonZeroUsize;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+use crate::message::message;
 
 fn convert_to_numeric_char(a: usize) -> u8 {
     // 最適化で消えるのでこの形で良い
@@ -34,10 +38,9 @@ pub fn pretty_size(bytes: usize) -> String {
         }
     }
 
-    // SAFETY: each value never equal to zero.
-    const GIB: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(1024 * 1024 * 1024) };
-    const MIB: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(1024 * 1024) };
-    const KIB: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(1024) };
+    const GIB: NonZeroUsize = NonZeroUsize::new(1024 * 1024 * 1024).unwrap();
+    const MIB: NonZeroUsize = NonZeroUsize::new(1024 * 1024).unwrap();
+    const KIB: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
 
     #[inline]
     fn prepare(bytes: usize, unit: NonZeroUsize) -> (usize, usize, usize) {
@@ -91,7 +94,7 @@ pub fn pretty_size(bytes: usize) -> String {
         if n >= pow {
             *bytes.get_unchecked_mut(byte_index) = convert_to_numeric_char(*r / pow);
             *r -= *r / pow * pow;
-            head.get_or_insert_with(|| (unsafe { NonZeroUsize::new_unchecked(byte_index + 1) }));
+            head.get_or_insert_with(|| unsafe { NonZeroUsize::new_unchecked(byte_index + 1) });
         }
     }
 
@@ -139,7 +142,7 @@ pub fn pretty_size(bytes: usize) -> String {
         bytes[6] = convert_to_numeric_char(rest_2);
         if M != 0 {
             bytes[7..(7 + M)].copy_from_slice(&unit_bytes);
-        };
+        }
 
         // SAFETY: we're just initialized `head` earlier, or a moment ago with fallback value.
         let head = unsafe { head.unwrap_unchecked() }.get() - 1;
@@ -151,7 +154,9 @@ pub fn pretty_size(bytes: usize) -> String {
     }
 
     if bytes > 100 * GIB.get() {
-        unreachable!("domain constraints");
+        // 上の速い経路は整数部が4桁までしか書けないので、いちばん大きな単位のまま素直に書く
+        let (n, rest) = (bytes / GIB.get(), bytes % GIB.get());
+        return format!("{n}.{fraction:02}GiB", fraction = rest / (GIB.get() / 100));
     }
 
     if bytes >= GIB.get() {
@@ -173,7 +178,7 @@ pub fn pretty_size(bytes: usize) -> String {
         // SAFETY: we have [u8; 5] which does not lead to out-of-bound access.
         *unsafe { buf.get_unchecked_mut(3) } = convert_to_numeric_char(rest % 10);
         // SAFETY: 4 != 0.
-        head_index.get_or_insert_with(|| (unsafe { NonZeroUsize::new_unchecked(3 + 1) }));
+        head_index.get_or_insert_with(|| unsafe { NonZeroUsize::new_unchecked(3 + 1) });
         // SAFETY: we have [u8; 5] which does not lead to out-of-bound access.
         *unsafe { buf.get_unchecked_mut(4) } = b'B';
         // SAFETY: we've just initialized head_index with some value.
@@ -183,14 +188,82 @@ pub fn pretty_size(bytes: usize) -> String {
     }
 }
 
+/// `500MiB`や`1.50GiB`のように書かれた大きさ。[`pretty_size`]の出力はそのまま読み戻せる。
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct ByteSize(pub usize);
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ParseSizeError {
+    #[error("{}", message!("error.size.empty"))]
+    Empty,
+    #[error("{}", message!("error.size.invalid-number", number = .0))]
+    InvalidNumber(String),
+    #[error("{}", message!("error.size.unknown-unit", unit = .0))]
+    UnknownUnit(String),
+    #[error("{}", message!("error.size.too-large"))]
+    TooLarge,
+}
+
+impl FromStr for ByteSize {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const MAX: u128 = 100 * 1024 * 1024 * 1024;
+
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseSizeError::Empty)
+        }
+
+        let split_at = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split_at);
+        let unit: u128 = match unit.trim() {
+            "" | "B" => 1,
+            "KiB" | "K" => 1024,
+            "MiB" | "M" => 1024 * 1024,
+            "GiB" | "G" => 1024 * 1024 * 1024,
+            other => return Err(ParseSizeError::UnknownUnit(other.to_string())),
+        };
+
+        let invalid_number = || ParseSizeError::InvalidNumber(number.to_string());
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if integer.is_empty() || fraction.len() > 9 || fraction.contains('.') {
+            return Err(invalid_number())
+        }
+
+        let integer = integer.parse::<u128>().map_err(|_| invalid_number())?;
+        let fraction_part = if fraction.is_empty() {
+            0
+        } else {
+            let digits = fraction.parse::<u128>().map_err(|_| invalid_number())?;
+            // 端数は切り捨て
+            digits * unit / 10u128.pow(fraction.len().try_into().expect("at most 9 digits"))
+        };
+
+        let bytes = integer.checked_mul(unit).and_then(|x| x.checked_add(fraction_part)).ok_or(ParseSizeError::TooLarge)?;
+        if bytes > MAX {
+            return Err(ParseSizeError::TooLarge)
+        }
+
+        Ok(Self(bytes.try_into().map_err(|_| ParseSizeError::TooLarge)?))
+    }
+}
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&pretty_size(self.0))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::time::Instant;
-    use crate::pretty_size::pretty_size;
+    use crate::pretty_size::{pretty_size, ByteSize, ParseSizeError};
 
     static TEST_BYTES: [usize; 19] =
         [0, 1, 9, 10, 99, 100, 999, 1000, 1023, 1024, 1536, 999_999, 1_000_000, 1_023_999, 1_024_000, 1024 * 1024, 999_999_999, 1_000_000_000, 1024 * 1024 * 1024];
 
+    #[allow(clippy::cast_precision_loss)]
     fn reference(bytes_base_of_two: usize) -> String {
         const KIB: f64 = 1_024.0f64;
         const MIB: f64 = KIB * 1_024.0f64;
@@ -212,7 +285,7 @@ mod test {
     fn test() {
         for q in TEST_BYTES {
             println!("{q}");
-            assert_eq!(reference(q), pretty_size(q))
+            assert_eq!(reference(q), pretty_size(q));
         }
     }
 
@@ -227,12 +300,12 @@ mod test {
 //         println!("i: {:?}", bench_i());
         println!("i2: {:?}", bench_i2());
         bench_ix();
-        println!("{}", reference(999999));
+        println!("{}", reference(999_999));
     }
 
     fn bench_f() -> std::time::Duration {
         let time = Instant::now();
-        for i in 0..1048576 {
+        for i in 0..1_048_576 {
             reference(i);
         }
 
@@ -241,7 +314,7 @@ mod test {
 
     fn bench_i2() -> std::time::Duration {
         let time = Instant::now();
-        for q in 0..1048576 {
+        for q in 0..1_048_576 {
             pretty_size(q);
         }
 
@@ -257,7 +330,35 @@ mod test {
             let i = pretty_size(i);
             let time_i = time.elapsed();
             assert_eq!(f, i);
-            println!("for {i}: float = {time_f:?} | int = {time_i:?}")
+            println!("for {i}: float = {time_f:?} | int = {time_i:?}");
+        }
+    }
+
+    #[test]
+    fn beyond_largest_unit() {
+        assert_eq!(pretty_size(101 * 1024 * 1024 * 1024), "101.00GiB");
+        assert_eq!(pretty_size(2048 * 1024 * 1024 * 1024 + 512 * 1024 * 1024), "2048.50GiB");
+        assert!(pretty_size(usize::MAX).ends_with("GiB"));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("500MiB".parse(), Ok(ByteSize(500 * 1024 * 1024)));
+        assert_eq!("1.50KiB".parse(), Ok(ByteSize(1536)));
+        assert_eq!("1 GiB".parse(), Ok(ByteSize(1024 * 1024 * 1024)));
+        assert_eq!("123".parse(), Ok(ByteSize(123)));
+        assert_eq!("".parse::<ByteSize>(), Err(ParseSizeError::Empty));
+        assert_eq!("1.2.3MiB".parse::<ByteSize>(), Err(ParseSizeError::InvalidNumber("1.2.3".to_string())));
+        assert_eq!("5MB".parse::<ByteSize>(), Err(ParseSizeError::UnknownUnit("MB".to_string())));
+        assert_eq!("101GiB".parse::<ByteSize>(), Err(ParseSizeError::TooLarge));
+    }
+
+    #[test]
+    fn round_trip() {
+        for b in TEST_BYTES {
+            let pretty = pretty_size(b);
+            let parsed = pretty.parse::<ByteSize>().unwrap();
+            assert_eq!(pretty, parsed.to_string());
         }
     }
-}
\ No newline at end of file
+}
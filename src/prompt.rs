@@ -0,0 +1,12 @@
+use std::io::{BufRead, Write};
+use crate::message;
+
+/// 書き込みや削除の前に確かめる。`y`か`yes`以外は断ったものとみなす。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn confirm(prompt: &str) -> std::io::Result<bool> {
+    print!("{prompt} {suffix} ", suffix = message::get("confirm.suffix"));
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
@@ -0,0 +1,94 @@
+use reqwest::Client;
+use crate::booth::{ItemId, ItemRef, PublicItem};
+use crate::{locale, ExecutionError};
+use crate::manage::USER_AGENT;
+use crate::message::message;
+
+const DEFAULT_BASE_URL: &str = "https://booth.pm";
+
+/// ログインせずに見られるbooth.pmのページ
+pub struct PublicClient {
+    client: Client,
+    base_url: String,
+}
+
+impl PublicClient {
+    pub fn new() -> Self {
+        let client = reqwest::ClientBuilder::new()
+            .gzip(true)
+            .build()
+            .unwrap();
+
+        // BOOTH_MANAGE_BASE_URLと同じく、テスト用のフィクスチャサーバーへ向けられるようにする
+        let base_url = std::env::var("BOOTH_PUBLIC_BASE_URL")
+            .map_or_else(|_| DEFAULT_BASE_URL.to_string(), |x| x.trim_end_matches('/').to_string());
+
+        Self { client, base_url }
+    }
+
+    /// 公開されていなければ`None`
+    pub async fn item(&self, id: ItemId) -> Result<Option<PublicItem>, ExecutionError> {
+        let language = locale::current().accept_language();
+        // URLの言語の部分は`Accept-Language`に送る値と同じ
+        let url = format!("{base}/{language}/items/{id}.json", base = self.base_url);
+        let res = self.client.get(url)
+            .header("Accept", "application/json")
+            .header("Accept-Language", language)
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await?;
+
+        if res.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None)
+        }
+
+        Ok(Some(res.error_for_status()?.json::<PublicItem>().await?))
+    }
+}
+
+fn print_item(item: &PublicItem) {
+    println!("{}", message!("public.name", name = item.name, id = item.id));
+    println!("{}", message!("public.shop", shop = item.shop.name, url = item.shop.url));
+    println!("{}", message!("public.price", price = item.price));
+    if let Some(category) = &item.category {
+        println!("{}", message!("public.category", category = category.name));
+    }
+    println!("{}", message!("public.tags", tags = item.tags.iter().map(|x| x.name.as_str()).collect::<Vec<_>>().join(", ")));
+    println!("{}", message!("public.wish-count", count = item.wish_count));
+    if let Some(published_at) = item.published_at {
+        println!("{}", message!("public.published-at", at = published_at.to_rfc3339()));
+    }
+    println!("{}", message!("public.images", count = item.images.len()));
+    println!("{}", message!("public.variations"));
+    for variation in &item.variations {
+        let stock = variation.stock.map_or_else(|| "-".to_string(), |x| x.to_string());
+        println!("{}", message!(
+            "public.variation",
+            id = variation.id,
+            name = variation.name.as_deref().unwrap_or("-"),
+            price = variation.price,
+            kind = variation.kind,
+            status = variation.status,
+            stock = stock,
+        ));
+    }
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn item(item: &ItemRef, json: bool) -> Result<(), ExecutionError> {
+    // 名前から探すにはショップの管理画面が要る
+    let ItemRef::Id(id) = item else {
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.public-item-by-name")))
+    };
+
+    let Some(item) = PublicClient::new().item(*id).await? else {
+        return Err(ExecutionError::NotPublic(*id))
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&item)?);
+    } else {
+        print_item(&item);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use crate::booth::{ItemId, UploadedObject};
+use crate::{ExecutionError, QuotaGuardArgs};
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::pretty_size::pretty_size;
+
+/// `*`と`?`だけを解釈する簡易的なワイルドカード
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    // matched[j]: patternの先頭i文字がnameの先頭j文字にマッチするか
+    let mut matched = vec![false; name.len() + 1];
+    matched[0] = true;
+
+    for p in pattern {
+        let mut next = vec![false; name.len() + 1];
+        match p {
+            '*' => {
+                let mut any = false;
+                for j in 0..=name.len() {
+                    any |= matched[j];
+                    next[j] = any;
+                }
+            }
+            _ => {
+                for j in 1..=name.len() {
+                    next[j] = matched[j - 1] && (p == '?' || p == name[j - 1]);
+                }
+            }
+        }
+        matched = next;
+    }
+
+    matched[name.len()]
+}
+
+/// `shortage`バイト以上を空けるために消すファイルを古い順に選ぶ。足りなければ`None`。
+/// IDは連番で振られるので、IDが小さいほど古いとみなす。
+fn select_eviction<'a>(files: &'a [UploadedObject], pattern: &str, protected: &[&str], shortage: usize) -> Option<Vec<&'a UploadedObject>> {
+    let mut candidates = files.iter()
+        .filter(|f| wildcard_match(pattern, &f.name) && !protected.contains(&f.name.as_str()))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|f| f.id);
+
+    let mut freed = 0;
+    let mut victims = vec![];
+    for file in candidates {
+        if freed >= shortage {
+            break
+        }
+        freed += file.file_size;
+        victims.push(file);
+    }
+
+    (freed >= shortage).then_some(victims)
+}
+
+/// アップロードの前に、`incoming`バイトを上げても`--min-free`以上の空きが残ることを確かめる。
+/// `protected`に含まれる名前のファイルは`--evict-oldest`の対象にしない。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn ensure_room(
+    client: &ManageClient,
+    booth_item_id: ItemId,
+    incoming: usize,
+    guard: &QuotaGuardArgs,
+    protected: &[&str],
+    csrf_token: &str,
+) -> Result<(), ExecutionError> {
+    let Some(min_free) = guard.min_free else {
+        return Ok(())
+    };
+
+    let list = client.downloadables(booth_item_id).await?;
+    let left = list.storage.left();
+    let required = incoming.saturating_add(min_free.0);
+    if left >= required {
+        return Ok(())
+    }
+
+    let quota_exceeded = || ExecutionError::QuotaExceeded { left, required };
+    let Some(pattern) = &guard.evict_oldest else {
+        return Err(quota_exceeded())
+    };
+
+    let victims = select_eviction(&list.files, pattern, protected, required - left).ok_or_else(quota_exceeded)?;
+    for victim in victims {
+        println!("{}", message!("quota.evicting", name = victim.name, size = pretty_size(victim.file_size)));
+        client.delete_downloadable(booth_item_id, victim.id, csrf_token).await?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn it(client: &ManageClient, booth_item_id: ItemId) -> Result<(), ExecutionError> {
+    let mut list = client.downloadables(booth_item_id).await?;
+    let storage = &list.storage;
+    println!("{}", message!("quota.permitted", size = pretty_size(storage.quota)));
+    println!("{}", message!("quota.used", size = pretty_size(storage.usage)));
+    println!("{}", message!("quota.left", size = pretty_size(storage.left())));
+
+    list.files.sort_by_key(|f| f.id);
+    let total = list.files.iter().map(|f| f.file_size).sum::<usize>();
+    println!("{}", message!("quota.item-usage", item = booth_item_id, size = pretty_size(total), count = list.files.len()));
+    for file in &list.files {
+        println!("  #{id} {name} ({size})", id = file.id, name = file.name, size = pretty_size(file.file_size));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::booth::UploadedObject;
+    use crate::fixture::uploaded;
+    use crate::quota::{select_eviction, wildcard_match};
+
+    #[test]
+    fn wildcard() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("app-*.zip", "app-1.0.0.zip"));
+        assert!(wildcard_match("app-?.zip", "app-1.zip"));
+        assert!(!wildcard_match("app-?.zip", "app-10.zip"));
+        assert!(!wildcard_match("*.zip", "readme.pdf"));
+        assert!(wildcard_match("*-*-*", "a-b-c"));
+    }
+
+    #[test]
+    fn eviction() {
+        let files = [uploaded(3, "app-3.zip", 30), uploaded(1, "app-1.zip", 10), uploaded(2, "app-2.zip", 20), uploaded(0, "manual.pdf", 100)];
+        let ids = |v: Vec<&UploadedObject>| v.iter().map(|f| f.id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(select_eviction(&files, "app-*.zip", &[], 25).map(ids), Some(vec!["1".to_string(), "2".to_string()]));
+        assert_eq!(select_eviction(&files, "app-*.zip", &["app-1.zip"], 25).map(ids), Some(vec!["2".to_string(), "3".to_string()]));
+        assert_eq!(select_eviction(&files, "app-*.zip", &[], 61).map(ids), None);
+        assert_eq!(select_eviction(&files, "app-*.zip", &[], 0).map(ids), Some(vec![]));
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::booth::{ItemDraft, ItemId, ItemRef, ParseItemRefError, PublishState, VariationDraft, JST};
+use crate::ExecutionError;
+use crate::items;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::prompt::confirm;
+
+const DEFAULT_LEDGER_FILE_NAME: &str = ".kisaragi-booth-sale.json";
+
+/// BOOTHで有料にできる最低の価格
+const MIN_PRICE: u32 = 100;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum SaleError {
+    #[error("{}", message!("error.sale.already-running", until = .0))]
+    AlreadyRunning(NaiveDate),
+    #[error("{}", message!("error.sale.not-running"))]
+    NotRunning,
+    #[error("{}", message!("error.sale.changed-externally", count = .0))]
+    ChangedExternally(usize),
+}
+
+/// `--items`の1つ。`all`は公開されているすべてのアイテム
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum SaleTarget {
+    All,
+    Item(ItemRef),
+}
+
+impl FromStr for SaleTarget {
+    type Err = ParseItemRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "all" {
+            Ok(Self::All)
+        } else {
+            s.parse().map(Self::Item)
+        }
+    }
+}
+
+/// セールの前の価格の控え。`sale end`はここから戻す。
+#[derive(Serialize, Deserialize, Default)]
+struct SaleLedger {
+    sale: Option<Sale>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Sale {
+    percent: u8,
+    started_at: DateTime<FixedOffset>,
+    until: NaiveDate,
+    /// 値下げを書き込み終えたアイテムだけ
+    items: BTreeMap<ItemId, Vec<PriceEntry>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PriceEntry {
+    variation_id: Option<u64>,
+    variation: String,
+    original: u32,
+    discounted: u32,
+}
+
+/// セールの後に誰かが変えた価格。`current`が`None`ならバリエーションがなくなっている
+#[derive(Eq, PartialEq, Debug)]
+pub struct Conflict {
+    variation: String,
+    expected: u32,
+    current: Option<u32>,
+}
+
+impl SaleLedger {
+    fn load(path: &Path) -> Result<Self, ExecutionError> {
+        if path.exists() {
+            let bytes = std::fs::read(path)?;
+            Ok(serde_json::from_slice(&bytes)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), ExecutionError> {
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// 1円未満は切り捨てる。無料のバリエーションと、最低価格を下回るものはそのまま
+fn discount(price: u32, percent: u8) -> u32 {
+    if price <= MIN_PRICE {
+        return price
+    }
+
+    // 割引後は元の価格を超えないので`u32`に収まる
+    let discounted = u32::try_from(u64::from(price) * u64::from(100 - percent) / 100).unwrap_or(price);
+    discounted.max(MIN_PRICE)
+}
+
+fn plan_start(draft: &ItemDraft, percent: u8) -> Vec<PriceEntry> {
+    draft.variations.iter()
+        .map(|v| PriceEntry { variation_id: v.id, variation: v.name.clone(), original: v.price, discounted: discount(v.price, percent) })
+        .filter(|x| x.original != x.discounted)
+        .collect()
+}
+
+/// IDがあればIDで、なければ名前で控えと対応させる
+fn is_recorded_as(variation: &VariationDraft, entry: &PriceEntry) -> bool {
+    entry.variation_id.map_or_else(|| variation.name == entry.variation, |id| variation.id == Some(id))
+}
+
+fn find_variation<'a>(draft: &'a ItemDraft, entry: &PriceEntry) -> Option<&'a VariationDraft> {
+    draft.variations.iter().find(|v| is_recorded_as(v, entry))
+}
+
+fn find_variation_mut<'a>(draft: &'a mut ItemDraft, entry: &PriceEntry) -> Option<&'a mut VariationDraft> {
+    draft.variations.iter_mut().find(|v| is_recorded_as(v, entry))
+}
+
+/// 値下げした価格のままになっている
+fn is_intact(draft: &ItemDraft, entry: &PriceEntry) -> bool {
+    find_variation(draft, entry).is_some_and(|v| v.price == entry.discounted)
+}
+
+/// 値下げした価格のままになっていないバリエーション
+fn conflicts(draft: &ItemDraft, entries: &[PriceEntry]) -> Vec<Conflict> {
+    entries.iter()
+        .filter(|entry| !is_intact(draft, entry))
+        .map(|entry| Conflict { variation: entry.variation.clone(), expected: entry.discounted, current: find_variation(draft, entry).map(|v| v.price) })
+        .collect()
+}
+
+fn today() -> NaiveDate {
+    Utc::now().with_timezone(&JST).date_naive()
+}
+
+async fn resolve_targets(client: &ManageClient, targets: &[SaleTarget]) -> Result<Vec<ItemId>, ExecutionError> {
+    let mut ids = vec![];
+    for target in targets {
+        match target {
+            SaleTarget::All => ids.extend(client.items().await?.into_iter().filter(|x| x.state == PublishState::Public).map(|x| x.id)),
+            SaleTarget::Item(item) => ids.push(items::resolve(client, item).await?),
+        }
+    }
+
+    let mut seen = std::collections::BTreeSet::new();
+    ids.retain(|id| seen.insert(*id));
+    Ok(ids)
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn start(
+    client: &ManageClient,
+    targets: &[SaleTarget],
+    percent: u8,
+    until: NaiveDate,
+    ledger_file: Option<&Path>,
+    yes: bool,
+    dry_run: bool,
+) -> Result<(), ExecutionError> {
+    let ledger_file = ledger_file.unwrap_or_else(|| Path::new(DEFAULT_LEDGER_FILE_NAME));
+    let mut ledger = SaleLedger::load(ledger_file)?;
+    if let Some(sale) = &ledger.sale {
+        return Err(SaleError::AlreadyRunning(sale.until).into())
+    }
+    if until < today() {
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.sale-until-past", until = until)))
+    }
+
+    let mut planned = vec![];
+    for booth_item_id in resolve_targets(client, targets).await? {
+        let (form, csrf_token) = client.edit_form(booth_item_id).await?;
+        let draft = ItemDraft::from_form(&form)?;
+        let entries = plan_start(&draft, percent);
+        for entry in &entries {
+            println!("{}", message!("sale.price", item = booth_item_id, variation = entry.variation, from = entry.original, to = entry.discounted));
+        }
+        if !entries.is_empty() {
+            planned.push((booth_item_id, form, csrf_token, draft, entries));
+        }
+    }
+
+    if planned.is_empty() {
+        println!("{}", message!("sale.nothing"));
+        return Ok(())
+    }
+    if dry_run {
+        return Ok(())
+    }
+    if !yes && !confirm(&message!("sale.confirm-start", count = planned.len(), percent = percent, until = until))? {
+        println!("{}", message!("confirm.aborted"));
+        return Ok(())
+    }
+
+    // 書き込みが途中で失敗しても、それまでのアイテムは`sale end`で戻せるように1つずつ控える
+    ledger.sale = Some(Sale { percent, started_at: Utc::now().with_timezone(&JST), until, items: BTreeMap::new() });
+    ledger.save(ledger_file)?;
+    let count = planned.len();
+    for (booth_item_id, mut form, csrf_token, mut draft, mut entries) in planned {
+        for entry in &entries {
+            if let Some(variation) = find_variation_mut(&mut draft, entry) {
+                variation.price = entry.discounted;
+            }
+        }
+        draft.apply_to(&mut form);
+        client.submit_form(&form, &csrf_token).await?;
+        if let Some(sale) = &mut ledger.sale {
+            sale.items.insert(booth_item_id, entries.clone());
+        }
+        ledger.save(ledger_file)?;
+
+        // BOOTHが丸めたり弾いたりした価格を控えないと、`sale end`が食い違いとして止まる。
+        // 読み直せなくても値下げは済んでいるので、控えた予定の価格のまま続ける
+        let saved = match client.edit_form(booth_item_id).await.and_then(|(form, _)| Ok(ItemDraft::from_form(&form)?)) {
+            Ok(saved) => saved,
+            Err(error) => {
+                eprintln!("{}", message!("sale.reread-failed", item = booth_item_id, error = error));
+                continue
+            }
+        };
+        let mut changed = false;
+        for entry in &mut entries {
+            let Some(actual) = find_variation(&saved, entry).map(|v| v.price) else {
+                continue
+            };
+            if actual != entry.discounted {
+                println!("{}", message!("sale.saved-differently", item = booth_item_id, variation = entry.variation, expected = entry.discounted, actual = actual));
+                entry.discounted = actual;
+                changed = true;
+            }
+        }
+        if let (true, Some(sale)) = (changed, &mut ledger.sale) {
+            sale.items.insert(booth_item_id, entries);
+            ledger.save(ledger_file)?;
+        }
+    }
+    println!("{}", message!("sale.started", count = count, percent = percent, until = until));
+
+    Ok(())
+}
+
+/// 控えた価格に戻す。セールの後に価格が変わっていれば、何も書き込まずに失敗する。
+/// `skip_conflicts`なら、変わった価格はそのままにして台帳から外し、残りを戻す。
+#[allow(clippy::redundant_pub_crate, clippy::fn_params_excessive_bools)]
+pub(crate) async fn end(client: &ManageClient, ledger_file: Option<&Path>, if_due: bool, skip_conflicts: bool, yes: bool, dry_run: bool) -> Result<(), ExecutionError> {
+    let ledger_file = ledger_file.unwrap_or_else(|| Path::new(DEFAULT_LEDGER_FILE_NAME));
+    let mut ledger = SaleLedger::load(ledger_file)?;
+    let Some(sale) = &ledger.sale else {
+        return Err(SaleError::NotRunning.into())
+    };
+    if if_due && today() <= sale.until {
+        println!("{}", message!("sale.not-due", until = sale.until));
+        return Ok(())
+    }
+
+    let mut planned = vec![];
+    let mut conflict_count = 0;
+    for (booth_item_id, entries) in &sale.items {
+        let (form, csrf_token) = client.edit_form(*booth_item_id).await?;
+        let draft = ItemDraft::from_form(&form)?;
+        let found = conflicts(&draft, entries);
+        for conflict in &found {
+            let current = conflict.current.map_or_else(|| "-".to_string(), |x| format!("¥{x}"));
+            println!("{}", message!("sale.conflict", item = booth_item_id, variation = conflict.variation, expected = conflict.expected, current = current));
+        }
+        conflict_count += found.len();
+        let entries = entries.iter().filter(|x| !skip_conflicts || is_intact(&draft, x)).cloned().collect::<Vec<_>>();
+        for entry in &entries {
+            println!("{}", message!("sale.price", item = booth_item_id, variation = entry.variation, from = entry.discounted, to = entry.original));
+        }
+        planned.push((*booth_item_id, form, csrf_token, draft, entries));
+    }
+
+    if conflict_count > 0 {
+        if !skip_conflicts {
+            return Err(SaleError::ChangedExternally(conflict_count).into())
+        }
+        println!("{}", message!("sale.conflicts-skipped", count = conflict_count));
+    }
+    if dry_run {
+        return Ok(())
+    }
+    if !yes && !confirm(&message!("sale.confirm-end", count = planned.len()))? {
+        println!("{}", message!("confirm.aborted"));
+        return Ok(())
+    }
+
+    let count = planned.len();
+    for (booth_item_id, mut form, csrf_token, mut draft, entries) in planned {
+        // 変わった価格しかなかったアイテムは、台帳から外すだけ
+        if !entries.is_empty() {
+            for entry in &entries {
+                if let Some(variation) = find_variation_mut(&mut draft, entry) {
+                    variation.price = entry.original;
+                }
+            }
+            draft.apply_to(&mut form);
+            client.submit_form(&form, &csrf_token).await?;
+        }
+        if let Some(sale) = &mut ledger.sale {
+            sale.items.remove(&booth_item_id);
+        }
+        ledger.save(ledger_file)?;
+    }
+    ledger.sale = None;
+    ledger.save(ledger_file)?;
+    println!("{}", message!("sale.ended", count = count));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::booth::{ItemDraft, ItemRef};
+    use crate::fixture::{self, variation};
+    use crate::sale::{conflicts, discount, plan_start, Conflict, PriceEntry, SaleTarget};
+
+    fn draft(prices: &[(u64, u32)]) -> ItemDraft {
+        fixture::draft(prices.iter().enumerate().map(|(i, (id, price))| variation(i, *id, &format!("v{id}"), *price)).collect())
+    }
+
+    #[test]
+    fn discounted_price() {
+        assert_eq!(discount(1000, 30), 700);
+        assert_eq!(discount(999, 30), 699);
+        assert_eq!(discount(0, 30), 0);
+        assert_eq!(discount(120, 50), 100);
+        assert_eq!(discount(100, 50), 100);
+    }
+
+    #[test]
+    fn start_and_end() {
+        let entries = plan_start(&draft(&[(1, 1000), (2, 0)]), 30);
+        assert_eq!(entries, [PriceEntry { variation_id: Some(1), variation: "v1".to_string(), original: 1000, discounted: 700 }]);
+
+        assert!(conflicts(&draft(&[(1, 700), (2, 0)]), &entries).is_empty());
+        assert_eq!(conflicts(&draft(&[(1, 800)]), &entries), [Conflict { variation: "v1".to_string(), expected: 700, current: Some(800) }]);
+        assert_eq!(conflicts(&draft(&[(2, 0)]), &entries), [Conflict { variation: "v1".to_string(), expected: 700, current: None }]);
+    }
+
+    #[test]
+    fn parse_target() {
+        assert_eq!("all".parse::<SaleTarget>().unwrap(), SaleTarget::All);
+        assert!(matches!("name:all".parse::<SaleTarget>().unwrap(), SaleTarget::Item(ItemRef::Name(name)) if name == "all"));
+    }
+}
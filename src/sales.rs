@@ -0,0 +1,303 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use chrono::{Datelike, NaiveDate, Weekday};
+use strum::EnumString;
+use crate::booth::{OrderRecord, JST};
+use crate::ExecutionError;
+use crate::message::{self, message};
+
+/// BOOTHのサービス利用料。注文ごとに価格の5.6%と22円がかかる
+const FEE_PER_MILLE: u64 = 56;
+const FEE_PER_ORDER: u64 = 22;
+/// 売上に数えない注文の状態
+pub const VOID_STATUSES: &[&str] = &["cancelled", "canceled", "refunded"];
+
+#[derive(EnumString, Copy, Clone, Eq, PartialEq, Debug)]
+#[strum(serialize_all = "lowercase")]
+pub enum GroupBy {
+    Item,
+    Variation,
+    Month,
+    Weekday,
+}
+
+#[derive(EnumString, Copy, Clone, Eq, PartialEq, Debug)]
+#[strum(serialize_all = "lowercase")]
+pub enum ReportFormat {
+    Table,
+    Csv,
+}
+
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+struct Totals {
+    quantity: u64,
+    revenue: u64,
+    fees: u64,
+}
+
+impl Totals {
+    const fn net(self) -> u64 {
+        self.revenue.saturating_sub(self.fees)
+    }
+}
+
+/// 並べる順序と表示する名前
+type Key = (String, String);
+
+fn key(record: &OrderRecord, by: GroupBy) -> Key {
+    let at = record.ordered_at.with_timezone(&JST);
+    match by {
+        // 売上の多い順に並べ直すので、順序は名前だけでよい
+        GroupBy::Item => (String::new(), format!("#{id} {name}", id = record.item_id, name = record.item_name)),
+        GroupBy::Variation => (String::new(), format!("#{id} {name} / {variation}", id = record.item_id, name = record.item_name, variation = record.variation)),
+        GroupBy::Month => (at.format("%Y-%m").to_string(), at.format("%Y-%m").to_string()),
+        GroupBy::Weekday => (at.weekday().num_days_from_monday().to_string(), weekday(at.weekday()).to_string()),
+    }
+}
+
+fn weekday(weekday: Weekday) -> &'static str {
+    message::get(match weekday {
+        Weekday::Mon => "sales.weekday.mon",
+        Weekday::Tue => "sales.weekday.tue",
+        Weekday::Wed => "sales.weekday.wed",
+        Weekday::Thu => "sales.weekday.thu",
+        Weekday::Fri => "sales.weekday.fri",
+        Weekday::Sat => "sales.weekday.sat",
+        Weekday::Sun => "sales.weekday.sun",
+    })
+}
+
+fn in_period(record: &OrderRecord, (from, to): (Option<NaiveDate>, Option<NaiveDate>)) -> bool {
+    let date = record.ordered_at.with_timezone(&JST).date_naive();
+    from.is_none_or(|from| from <= date) && to.is_none_or(|to| date <= to)
+}
+
+fn aggregate(records: &[OrderRecord], by: GroupBy, period: (Option<NaiveDate>, Option<NaiveDate>)) -> BTreeMap<Key, Totals> {
+    let mut totals = BTreeMap::<Key, Totals>::new();
+    let mut charged_orders = HashSet::new();
+
+    for record in records.iter().filter(|x| in_period(x, period) && !VOID_STATUSES.contains(&x.status.as_str())) {
+        let price = u64::from(record.price);
+        let mut fee = price * FEE_PER_MILLE / 1000;
+        // 一つの注文で複数の商品を買っても、固定の利用料は一度だけ
+        if charged_orders.insert(record.order_id) {
+            fee += FEE_PER_ORDER;
+        }
+
+        let entry = totals.entry(key(record, by)).or_default();
+        entry.quantity += 1;
+        entry.revenue += price;
+        entry.fees += fee;
+    }
+
+    totals
+}
+
+/// 集計した一行。比較する期間がなければ`previous`は`None`
+struct Row {
+    label: String,
+    current: Totals,
+    previous: Option<Totals>,
+}
+
+fn report(records: &[OrderRecord], by: GroupBy, period: (Option<NaiveDate>, Option<NaiveDate>), compare: Option<(NaiveDate, NaiveDate)>) -> Vec<Row> {
+    let current = aggregate(records, by, period);
+    let previous = compare.map(|(from, to)| aggregate(records, by, (Some(from), Some(to))));
+
+    let mut keys = current.keys().chain(previous.iter().flat_map(BTreeMap::keys)).cloned().collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+
+    let mut rows = keys.into_iter().map(|key| Row {
+        current: current.get(&key).copied().unwrap_or_default(),
+        previous: previous.as_ref().map(|x| x.get(&key).copied().unwrap_or_default()),
+        label: key.1,
+    }).collect::<Vec<_>>();
+
+    if matches!(by, GroupBy::Item | GroupBy::Variation) {
+        rows.sort_by(|a, b| b.current.revenue.cmp(&a.current.revenue).then_with(|| a.label.cmp(&b.label)));
+    }
+
+    rows
+}
+
+/// 前の期間からの売上の増減。前の期間に売上がなければ出さない
+fn change(row: &Row) -> String {
+    let Some(previous) = row.previous.filter(|x| x.revenue > 0) else {
+        return "-".to_string()
+    };
+
+    let current = i128::from(row.current.revenue);
+    let previous = i128::from(previous.revenue);
+    let per_mille = (current - previous) * 1000 / previous;
+    format!("{sign}{integer}.{fraction}%", sign = if per_mille < 0 { "-" } else { "+" }, integer = per_mille.abs() / 10, fraction = per_mille.abs() % 10)
+}
+
+fn cells(row: &Row) -> Vec<String> {
+    let mut cells = vec![
+        row.label.clone(),
+        row.current.quantity.to_string(),
+        row.current.revenue.to_string(),
+        row.current.fees.to_string(),
+        row.current.net().to_string(),
+    ];
+    if let Some(previous) = row.previous {
+        cells.extend([previous.quantity.to_string(), previous.revenue.to_string(), previous.net().to_string(), change(row)]);
+    }
+
+    cells
+}
+
+fn headers(compare: bool) -> Vec<&'static str> {
+    let mut headers = vec![
+        message::get("sales.column.key"),
+        message::get("sales.column.quantity"),
+        message::get("sales.column.revenue"),
+        message::get("sales.column.fees"),
+        message::get("sales.column.net"),
+    ];
+    if compare {
+        headers.extend([
+            message::get("sales.column.previous-quantity"),
+            message::get("sales.column.previous-revenue"),
+            message::get("sales.column.previous-net"),
+            message::get("sales.column.change"),
+        ]);
+    }
+
+    headers
+}
+
+fn render_table(rows: &[Row], compare: bool) -> String {
+    let headers = headers(compare).into_iter().map(ToString::to_string).collect::<Vec<_>>();
+    let body = rows.iter().map(cells).collect::<Vec<_>>();
+    let width = |i: usize| std::iter::once(&headers).chain(&body).map(|x| x[i].chars().count()).max().unwrap_or_default();
+    let widths = (0..headers.len()).map(width).collect::<Vec<_>>();
+
+    let mut table = String::new();
+    for line in std::iter::once(&headers).chain(&body) {
+        let padded = line.iter().enumerate().map(|(i, cell)| {
+            let padding = " ".repeat(widths[i] - cell.chars().count());
+            // 最初の列は左寄せ、数値の列は右寄せ
+            if i == 0 { format!("{cell}{padding}") } else { format!("{padding}{cell}") }
+        }).collect::<Vec<_>>();
+        table.push_str(padded.join("  ").trim_end());
+        table.push('\n');
+    }
+
+    table
+}
+
+fn read_orders(path: &Path) -> Result<Vec<OrderRecord>, ExecutionError> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    if path.extension().is_some_and(|x| x.eq_ignore_ascii_case("json")) {
+        Ok(serde_json::from_reader(file)?)
+    } else {
+        Ok(csv::Reader::from_reader(file).deserialize().collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+/// `orders export`で書き出したファイルだけから集計する。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn it(
+    orders: &Path,
+    by: GroupBy,
+    period: (Option<NaiveDate>, Option<NaiveDate>),
+    compare: Option<(NaiveDate, NaiveDate)>,
+    format: ReportFormat,
+) -> Result<(), ExecutionError> {
+    // 月ごとの行は期間が違えば重ならないので、比べても前の期間の列が0になるだけ
+    if compare.is_some() && by == GroupBy::Month {
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.compare-by-month")))
+    }
+
+    let records = read_orders(orders)?;
+    let rows = report(&records, by, period, compare);
+
+    match format {
+        ReportFormat::Table => print!("{}", render_table(&rows, compare.is_some())),
+        ReportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout().lock());
+            writer.write_record(headers(compare.is_some()))?;
+            for row in &rows {
+                writer.write_record(cells(row))?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, NaiveDate};
+    use crate::booth::{ItemId, OrderRecord};
+    use crate::message;
+    use crate::sales::{aggregate, change, report, GroupBy, Row, Totals};
+
+    fn order(order_id: u64, at: &str, item: u32, variation: &str, price: u32, status: &str) -> OrderRecord {
+        OrderRecord {
+            order_id,
+            ordered_at: DateTime::parse_from_rfc3339(at).unwrap(),
+            item_id: ItemId::new(item).unwrap(),
+            item_name: format!("Item {item}"),
+            variation: variation.to_string(),
+            price,
+            payment_method: "credit_card".to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    fn records() -> Vec<OrderRecord> {
+        vec![
+            // 2026-08-31 23:30 JST は8月
+            order(1, "2026-08-31T14:30:00Z", 1, "Basic", 1000, "completed"),
+            order(2, "2026-09-01T10:00:00+09:00", 1, "Basic", 1000, "completed"),
+            order(2, "2026-09-01T10:00:00+09:00", 2, "Pro", 2000, "completed"),
+            order(3, "2026-09-07T10:00:00+09:00", 1, "Pro", 3000, "cancelled"),
+            order(4, "2026-09-08T10:00:00+09:00", 2, "Pro", 2000, "completed"),
+        ]
+    }
+
+    #[test]
+    fn by_month_with_fees() {
+        let totals = aggregate(&records(), GroupBy::Month, (None, None)).into_iter().map(|(k, v)| (k.1, v)).collect::<Vec<_>>();
+        assert_eq!(totals, [
+            ("2026-08".to_string(), Totals { quantity: 1, revenue: 1000, fees: 56 + 22 }),
+            // 注文2の固定の利用料は一度だけ。キャンセルは数えない
+            ("2026-09".to_string(), Totals { quantity: 3, revenue: 5000, fees: 56 + 112 + 22 + 112 + 22 }),
+        ]);
+    }
+
+    #[test]
+    fn by_item_sorted_by_revenue() {
+        let rows = report(&records(), GroupBy::Item, (None, None), None);
+        assert_eq!(rows.iter().map(|x| (x.label.as_str(), x.current.revenue)).collect::<Vec<_>>(), [("#2 Item 2", 4000), ("#1 Item 1", 2000)]);
+    }
+
+    #[test]
+    fn by_weekday() {
+        let rows = report(&records(), GroupBy::Weekday, (None, None), None);
+        assert_eq!(rows.iter().map(|x| (x.label.as_str(), x.current.quantity)).collect::<Vec<_>>(), [
+            (message::get("sales.weekday.mon"), 1),
+            (message::get("sales.weekday.tue"), 3),
+        ]);
+    }
+
+    #[test]
+    fn compare_periods() {
+        let date = |x: &str| x.parse::<NaiveDate>().unwrap();
+        let period = (Some(date("2026-09-01")), Some(date("2026-09-30")));
+        let rows = report(&records(), GroupBy::Variation, period, Some((date("2026-08-01"), date("2026-08-31"))));
+        assert_eq!(rows.iter().map(|x| (x.label.as_str(), x.current.revenue, x.previous.map(|p| p.revenue))).collect::<Vec<_>>(), [
+            ("#2 Item 2 / Pro", 4000, Some(0)),
+            ("#1 Item 1 / Basic", 1000, Some(1000)),
+        ]);
+        assert_eq!(change(&rows[1]), "+0.0%");
+        assert_eq!(change(&rows[0]), "-");
+
+        let row = Row { label: String::new(), current: Totals { quantity: 1, revenue: 900, fees: 0 }, previous: Some(Totals { quantity: 1, revenue: 1200, fees: 0 }) };
+        assert_eq!(change(&row), "-25.0%");
+    }
+}
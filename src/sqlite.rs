@@ -1,8 +1,10 @@
+use std::fmt::Display;
 use std::num::NonZeroUsize;
 use std::path::Path;
 use sqlite3::Error;
 use thiserror::Error;
 use crate::{Browser, ExecutionError, GetAuthorizationTokenError};
+use crate::message::message;
 
 #[derive(Error, Debug)]
 #[error("sqlite3 error (code {code:?}): {message:?}")]
@@ -22,6 +24,17 @@ impl PartialEq<Self> for SQLite3ErrorWithCompare {
 
 impl Eq for SQLite3ErrorWithCompare {}
 
+impl SQLite3ErrorWithCompare {
+    /// 書き込めない値か、書いたはずのない値
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) fn out_of_range(value: impl Display) -> Self {
+        Self {
+            code: None,
+            message: Some(format!("value out of range: {value}")),
+        }
+    }
+}
+
 impl From<Error> for SQLite3ErrorWithCompare {
     fn from(value: Error) -> Self {
         Self {
@@ -31,23 +44,43 @@ impl From<Error> for SQLite3ErrorWithCompare {
     }
 }
 
+// SQLiteの整数は符号付き64ビット
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn to_sql(value: u64) -> Result<i64, SQLite3ErrorWithCompare> {
+    i64::try_from(value).map_err(|_| SQLite3ErrorWithCompare::out_of_range(value))
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn from_sql<T: TryFrom<i64>>(value: i64) -> Result<T, SQLite3ErrorWithCompare> {
+    T::try_from(value).map_err(|_| SQLite3ErrorWithCompare::out_of_range(value))
+}
+
+/// `null`なら`None`
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn read_optional<T: TryFrom<i64>>(statement: &sqlite3::Statement, i: usize) -> Result<Option<T>, SQLite3ErrorWithCompare> {
+    match statement.read::<sqlite3::Value>(i)? {
+        sqlite3::Value::Integer(value) => from_sql(value).map(Some),
+        _ => Ok(None),
+    }
+}
+
 #[allow(clippy::redundant_pub_crate)]
 pub(crate) fn it(cookie_file: impl AsRef<Path>, browser: Browser) -> Result<(), ExecutionError> {
     let cookie_file = cookie_file.as_ref();
 
     if !cookie_file.exists() {
-        return Err(ExecutionError::CommandLineArgumentValidation("--cookie-file must point to existing path".to_string()))
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.cookie-file-not-found")))
     }
 
     if cookie_file.is_dir() {
-        return Err(ExecutionError::CommandLineArgumentValidation("--cookie-file must point to file".to_string()))
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.cookie-file-is-dir")))
     }
 
     let call_sql = || {
         match browser {
-            Browser::Firefox => r#"select value from moz_cookies where host = '.booth.pm' and name = '_plaza_session_nktz7u';"#,
-            Browser::Chromium => r#"select value from cookies where host = '.booth.pm' and name = '_plaza_session_nktz7u'"#,
-            Browser::UnsupportedBrowser(_) => unreachable!()
+            Browser::Firefox => r"select value from moz_cookies where host = '.booth.pm' and name = '_plaza_session_nktz7u';",
+            Browser::Chromium => r"select value from cookies where host = '.booth.pm' and name = '_plaza_session_nktz7u'",
+            Browser::Unsupported(_) => unreachable!()
         }
     };
     let handle = || {
@@ -70,8 +103,8 @@ pub(crate) fn it(cookie_file: impl AsRef<Path>, browser: Browser) -> Result<(),
         Browser::Chromium | Browser::Firefox => {
             handle()?
         }
-        Browser::UnsupportedBrowser(browser) => {
-            return Err(ExecutionError::CommandLineArgumentValidation(format!("{browser} is not supported yet.")))
+        Browser::Unsupported(browser) => {
+            return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.unsupported-browser", browser = browser)))
         }
     };
 
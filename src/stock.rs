@@ -0,0 +1,101 @@
+use serde::Serialize;
+use thiserror::Error;
+use crate::booth::{ItemDraft, ItemId};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum StockError {
+    #[error("{}", message!("error.stock.not-tracked", variation = .0))]
+    NotTracked(String),
+    /// 読んだ後に誰かが在庫を増やした。スクリプトの前提が崩れているので上書きしない
+    #[error("{}", message!("error.stock.increased", observed = .observed, current = .current))]
+    IncreasedSinceObserved { observed: u32, current: u32 },
+}
+
+/// 書き込む在庫の数。`decrement_on`はスクリプトが在庫を読んだときの数で、それから売れた分を`target`から引く。
+const fn plan(current: u32, target: u32, decrement_on: Option<u32>) -> Result<u32, StockError> {
+    let Some(observed) = decrement_on else {
+        return Ok(target)
+    };
+
+    if current > observed {
+        return Err(StockError::IncreasedSinceObserved { observed, current })
+    }
+
+    Ok(target.saturating_sub(observed - current))
+}
+
+#[derive(Serialize)]
+struct VariationStock<'a> {
+    id: Option<u64>,
+    name: &'a str,
+    stock: Option<u32>,
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn get(client: &ManageClient, booth_item_id: ItemId, variation: Option<&str>, json: bool) -> Result<(), ExecutionError> {
+    let (form, _) = client.edit_form(booth_item_id).await?;
+    let mut draft = ItemDraft::from_form(&form)?;
+    if let Some(variation) = variation {
+        let found = draft.variation_mut(variation)?.clone();
+        draft.variations = vec![found];
+    }
+
+    let stocks = draft.variations.iter().map(|x| VariationStock { id: x.id, name: &x.name, stock: x.stock }).collect::<Vec<_>>();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stocks)?);
+        return Ok(())
+    }
+
+    for stock in &stocks {
+        let id = stock.id.map_or_else(|| "-".to_string(), |x| x.to_string());
+        let count = stock.stock.map_or_else(|| "-".to_string(), |x| x.to_string());
+        println!("{}", message!("stock.row", id = id, name = stock.name, stock = count));
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn set(client: &ManageClient, booth_item_id: ItemId, variation: &str, target: u32, decrement_on: Option<u32>) -> Result<(), ExecutionError> {
+    let (mut form, csrf) = client.edit_form(booth_item_id).await?;
+    let mut draft = ItemDraft::from_form(&form)?;
+    let found = draft.variation_mut(variation)?;
+    let Some(current) = found.stock else {
+        return Err(StockError::NotTracked(found.name.clone()).into())
+    };
+
+    let stock = plan(current, target, decrement_on)?;
+    if let Some(observed) = decrement_on.filter(|x| *x != current) {
+        println!("{}", message!("stock.sold-since", sold = observed - current, observed = observed));
+    }
+    if stock == current {
+        println!("{}", message!("stock.unchanged", name = found.name, stock = stock));
+        return Ok(())
+    }
+
+    found.stock = Some(stock);
+    let name = found.name.clone();
+    draft.apply_to(&mut form);
+    client.submit_form(&form, &csrf).await?;
+    println!("{}", message!("stock.updated", name = name, from = current, to = stock));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stock::{plan, StockError};
+
+    #[test]
+    fn restock() {
+        assert_eq!(plan(3, 10, None), Ok(10));
+        // 読んでから2個売れたので、その分を引く
+        assert_eq!(plan(3, 10, Some(5)), Ok(8));
+        assert_eq!(plan(5, 10, Some(5)), Ok(10));
+        assert_eq!(plan(0, 1, Some(5)), Ok(0));
+        assert_eq!(plan(7, 10, Some(5)), Err(StockError::IncreasedSinceObserved { observed: 5, current: 7 }));
+    }
+}
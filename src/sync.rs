@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::booth::{ItemId, UploadResult, UploadedObject};
+use crate::{quota, ExecutionError, QuotaGuardArgs};
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::prompt::confirm;
+use crate::pretty_size::pretty_size;
+
+const DEFAULT_STATE_FILE_NAME: &str = ".kisaragi-booth-sync.json";
+
+/// 前回の同期でアップロードしたファイルの記録。アイテムID→ファイル名→内容の順で引く。
+#[derive(Serialize, Deserialize, Default)]
+struct SyncState {
+    items: BTreeMap<ItemId, BTreeMap<String, SyncedFile>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+struct SyncedFile {
+    size: usize,
+    sha256: String,
+}
+
+impl SyncState {
+    fn load(path: &Path) -> Result<Self, ExecutionError> {
+        if path.exists() {
+            let bytes = std::fs::read(path)?;
+            Ok(serde_json::from_slice(&bytes)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), ExecutionError> {
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct LocalFile {
+    path: PathBuf,
+    name: String,
+    content: SyncedFile,
+}
+
+impl LocalFile {
+    fn read(path: PathBuf) -> Result<Self, ExecutionError> {
+        let bytes = std::fs::read(&path)?;
+        let name = path.file_name()
+            .and_then(|x| x.to_str())
+            .ok_or_else(|| ExecutionError::CommandLineArgumentValidation(message!("validation.non-utf8-name", path = path.display())))?
+            .to_string();
+
+        Ok(Self {
+            path,
+            name,
+            content: SyncedFile {
+                size: bytes.len(),
+                sha256: sha256_hex(&bytes),
+            },
+        })
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    Sha256::digest(bytes).iter().fold(String::with_capacity(64), |mut acc, b| {
+        write!(acc, "{b:02x}").expect("writing to String never fails");
+        acc
+    })
+}
+
+#[derive(Eq, PartialEq, Debug)]
+enum Step<'a> {
+    /// リモートに同名のファイルがない
+    Upload { local: &'a LocalFile },
+    /// 同名のファイルがあるが、サイズか内容が異なる。新しいものを上げてから古いものを消す。
+    Replace { local: &'a LocalFile, remote: &'a UploadedObject },
+    Keep { local: &'a LocalFile },
+    /// ローカルに存在しないリモートのファイル
+    Prune { remote: &'a UploadedObject },
+}
+
+/// `recorded`は前回の同期で記録した内容。記録がなければ名前とサイズだけで同一とみなす。
+fn plan<'a>(
+    local: &'a [LocalFile],
+    remote: &'a [UploadedObject],
+    recorded: &BTreeMap<String, SyncedFile>,
+    prune: bool,
+) -> Vec<Step<'a>> {
+    let mut steps = local.iter().map(|local| {
+        // 置き換えの途中で止まると同名のファイルが残るので、後からアップロードしたものを比べる
+        let Some(remote) = remote.iter().filter(|r| r.name == local.name).max_by_key(|r| r.id) else {
+            return Step::Upload { local }
+        };
+
+        let same_size = remote.file_size == local.content.size;
+        let same_content = recorded.get(&local.name).is_none_or(|r| r.sha256 == local.content.sha256);
+
+        if same_size && same_content {
+            Step::Keep { local }
+        } else {
+            Step::Replace { local, remote }
+        }
+    }).collect::<Vec<_>>();
+
+    // 古い方の同名のファイルは`--prune`がなくても消す
+    steps.extend(
+        remote.iter()
+            .filter(|r| local.iter().any(|l| l.name == r.name) && remote.iter().any(|x| x.name == r.name && x.id > r.id))
+            .map(|remote| Step::Prune { remote })
+    );
+
+    if prune {
+        steps.extend(
+            remote.iter()
+                .filter(|r| !local.iter().any(|l| l.name == r.name))
+                .map(|remote| Step::Prune { remote })
+        );
+    }
+
+    steps
+}
+
+fn print_plan(booth_item_id: ItemId, steps: &[Step<'_>]) {
+    println!("{}", message!("sync.plan", item = booth_item_id));
+    for step in steps {
+        match step {
+            Step::Upload { local } => println!("{}", message!("sync.plan.upload", name = local.name, size = pretty_size(local.content.size))),
+            Step::Replace { local, remote } => println!("{}", message!(
+                "sync.plan.replace",
+                name = local.name,
+                old = pretty_size(remote.file_size),
+                new = pretty_size(local.content.size),
+            )),
+            Step::Keep { local } => println!("{}", message!("sync.plan.keep", name = local.name)),
+            Step::Prune { remote } => println!("{}", message!("sync.plan.prune", name = remote.name, size = pretty_size(remote.file_size))),
+        }
+    }
+}
+
+#[allow(clippy::redundant_pub_crate, clippy::fn_params_excessive_bools, clippy::too_many_arguments)]
+pub(crate) async fn it(
+    client: &ManageClient,
+    booth_item_id: ItemId,
+    dir: &Path,
+    state_file: Option<PathBuf>,
+    prune: bool,
+    yes: bool,
+    dry_run: bool,
+    quota_guard: &QuotaGuardArgs,
+) -> Result<(), ExecutionError> {
+    if !dir.is_dir() {
+        return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.dir-not-found")))
+    }
+
+    let state_file = state_file.unwrap_or_else(|| dir.join(DEFAULT_STATE_FILE_NAME));
+    let mut state = SyncState::load(&state_file)?;
+
+    let mut local = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path == state_file {
+            continue
+        }
+        local.push(LocalFile::read(path)?);
+    }
+    local.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let remote = client.downloadables(booth_item_id).await?;
+    println!("{}", message!("sync.quota-left", left = pretty_size(remote.storage.left())));
+    let remote = remote.files;
+    let recorded = state.items.remove(&booth_item_id).unwrap_or_default();
+    let steps = plan(&local, &remote, &recorded, prune);
+    print_plan(booth_item_id, &steps);
+
+    if dry_run || steps.iter().all(|s| matches!(s, Step::Keep { .. })) {
+        return Ok(())
+    }
+
+    let prune_count = steps.iter().filter(|s| matches!(s, Step::Prune { .. })).count();
+    if prune_count > 0 && !yes && !confirm(&message!("sync.confirm-prune", count = prune_count))? {
+        println!("{}", message!("confirm.aborted"));
+        return Ok(())
+    }
+
+    let csrf_token = client.csrf_token(booth_item_id).await?;
+    let incoming = steps.iter().filter_map(|s| match s {
+        Step::Upload { local } | Step::Replace { local, .. } => Some(local.content.size),
+        _ => None,
+    }).sum();
+    // 計画に含まれるファイルを先に消してしまわないようにする
+    let protected = local.iter().map(|l| l.name.as_str())
+        .chain(steps.iter().filter_map(|s| match s {
+            Step::Prune { remote } => Some(remote.name.as_str()),
+            _ => None,
+        }))
+        .collect::<Vec<_>>();
+    quota::ensure_room(client, booth_item_id, incoming, quota_guard, &protected, &csrf_token).await?;
+
+    let mut synced = recorded.clone();
+    let mut remote_now = None;
+
+    for step in &steps {
+        match step {
+            Step::Upload { local } | Step::Replace { local, .. } => {
+                println!("{}", message!("sync.uploading", name = local.name));
+                match client.upload(booth_item_id, &local.path, &csrf_token).await? {
+                    UploadResult::Ok { uploaded_in_past, storage, uploaded_file } => {
+                        println!("{}", message!("sync.uploaded", name = uploaded_file.name, size = pretty_size(uploaded_file.file_size), left = pretty_size(storage.left())));
+                        let mut files = uploaded_in_past;
+                        files.push(uploaded_file);
+                        remote_now = Some(files);
+                    }
+                    UploadResult::Err(error) => {
+                        // ここまでの進捗は残しておく
+                        state.items.insert(booth_item_id, synced);
+                        state.save(&state_file)?;
+                        return Err(error.into())
+                    }
+                }
+
+                synced.insert(local.name.clone(), local.content.clone());
+
+                if let Step::Replace { remote, .. } = step {
+                    println!("{}", message!("sync.deleting-old", name = remote.name));
+                    if let Err(error) = client.delete_downloadable(booth_item_id, remote.id, &csrf_token).await {
+                        // 新しいファイルは上がっているので記録しておく。古いファイルは次の同期で消す
+                        state.items.insert(booth_item_id, synced);
+                        state.save(&state_file)?;
+                        return Err(error)
+                    }
+                }
+            }
+            Step::Keep { local } => {
+                synced.insert(local.name.clone(), local.content.clone());
+            }
+            Step::Prune { remote } => {
+                println!("{}", message!("sync.deleting", name = remote.name));
+                client.delete_downloadable(booth_item_id, remote.id, &csrf_token).await?;
+                synced.remove(&remote.name);
+            }
+        }
+    }
+
+    synced.retain(|name, _| local.iter().any(|l| &l.name == name));
+    state.items.insert(booth_item_id, synced);
+    state.save(&state_file)?;
+
+    if let Some(files) = remote_now {
+        let deleted = steps.iter().filter_map(|s| match s {
+            Step::Replace { remote, .. } | Step::Prune { remote } => Some(remote.id),
+            _ => None,
+        }).collect::<Vec<_>>();
+        let count = files.iter().filter(|f| !deleted.contains(&f.id)).count();
+        println!("{}", message!("sync.summary", item = booth_item_id, count = count));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use crate::fixture::uploaded;
+    use crate::sync::{plan, sha256_hex, LocalFile, Step, SyncState, SyncedFile};
+
+    fn local(name: &str, content: &[u8]) -> LocalFile {
+        LocalFile {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            content: SyncedFile { size: content.len(), sha256: sha256_hex(content) },
+        }
+    }
+
+    #[test]
+    fn sha256() {
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn plan_without_record() {
+        let local = [local("a.zip", b"aaa"), local("b.zip", b"bbbb"), local("c.zip", b"c")];
+        let remote = [uploaded(1, "a.zip", 3), uploaded(2, "b.zip", 3), uploaded(3, "old.zip", 10)];
+        let steps = plan(&local, &remote, &BTreeMap::new(), false);
+        assert_eq!(steps, [
+            Step::Keep { local: &local[0] },
+            Step::Replace { local: &local[1], remote: &remote[1] },
+            Step::Upload { local: &local[2] },
+        ]);
+
+        let steps = plan(&local, &remote, &BTreeMap::new(), true);
+        assert_eq!(steps.last(), Some(&Step::Prune { remote: &remote[2] }));
+    }
+
+    #[test]
+    fn plan_detects_same_size_change() {
+        let local = [local("a.zip", b"new")];
+        let remote = [uploaded(1, "a.zip", 3)];
+        let recorded = BTreeMap::from([("a.zip".to_string(), SyncedFile { size: 3, sha256: sha256_hex(b"old") })]);
+        let steps = plan(&local, &remote, &recorded, false);
+        assert_eq!(steps, [Step::Replace { local: &local[0], remote: &remote[0] }]);
+    }
+
+    #[test]
+    fn plan_removes_leftover_of_interrupted_replace() {
+        let local = [local("a.zip", b"new")];
+        let remote = [uploaded(1, "a.zip", 4), uploaded(2, "a.zip", 3)];
+        let recorded = BTreeMap::from([("a.zip".to_string(), SyncedFile { size: 3, sha256: sha256_hex(b"new") })]);
+        let steps = plan(&local, &remote, &recorded, false);
+        assert_eq!(steps, [Step::Keep { local: &local[0] }, Step::Prune { remote: &remote[0] }]);
+    }
+
+    #[test]
+    fn state_file_format() {
+        // 以前の`i32`のキーと同じ形で読み書きできる
+        let json = r#"{"items":{"3519955":{"app.zip":{"size":1,"sha256":"00"}}}}"#;
+        let state = serde_json::from_str::<SyncState>(json).unwrap();
+        assert_eq!(serde_json::to_string(&state).unwrap(), json);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::booth::{FileId, ItemDraft, ItemId, UploadedObject};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::pretty_size::pretty_size;
+
+/// IDか名前でアップロード済みのファイルを探す。
+fn find_file<'a>(files: &'a [UploadedObject], id_or_name: &str) -> Result<&'a UploadedObject, ExecutionError> {
+    files.iter()
+        .find(|f| f.id.to_string() == id_or_name)
+        .or_else(|| files.iter().find(|f| f.name == id_or_name))
+        .ok_or_else(|| ExecutionError::CommandLineArgumentValidation(message!("validation.unknown-downloadable", file = id_or_name)))
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn list(client: &ManageClient, booth_item_id: ItemId) -> Result<(), ExecutionError> {
+    let (form, _) = client.edit_form(booth_item_id).await?;
+    let draft = ItemDraft::from_form(&form)?;
+    let files = client.downloadables(booth_item_id).await?.files;
+
+    for variation in &draft.variations {
+        let id = variation.id.map_or_else(|| "-".to_string(), |id| id.to_string());
+        let kind = variation.kind.as_deref().unwrap_or("-");
+        println!("#{id} {name} ({kind}): ¥{price}", name = variation.name, price = variation.price);
+        for file_id in &variation.downloadable_ids {
+            match files.iter().find(|f| f.id == *file_id) {
+                Some(file) => println!("  #{file_id} {name} ({size})", name = file.name, size = pretty_size(file.file_size)),
+                None => println!("  #{file_id}"),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `variation`に`files`を紐付ける(`attach`)か、外す。既に紐付いているものや紐付いていないものは無視する。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn link(client: &ManageClient, booth_item_id: ItemId, variation: &str, files: &[FileId], attach: bool) -> Result<(), ExecutionError> {
+    let (mut form, csrf_token) = client.edit_form(booth_item_id).await?;
+    let mut draft = ItemDraft::from_form(&form)?;
+    let target = draft.variation_mut(variation)?;
+    let before = target.downloadable_ids.clone();
+
+    if attach {
+        target.downloadable_ids.extend(files.iter().filter(|f| !before.contains(f)));
+    } else {
+        target.downloadable_ids.retain(|f| !files.contains(f));
+    }
+
+    let name = target.name.clone();
+    let count = target.downloadable_ids.len().abs_diff(before.len());
+    if count == 0 {
+        println!("{}", message!("variation.unchanged", variation = name));
+        return Ok(())
+    }
+
+    draft.apply_to(&mut form);
+    client.submit_form(&form, &csrf_token).await?;
+    if attach {
+        println!("{}", message!("variation.attached", variation = name, count = count));
+    } else {
+        println!("{}", message!("variation.detached", variation = name, count = count));
+    }
+
+    Ok(())
+}
+
+/// `--file`に指定されたIDか名前をファイルのIDにする。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn resolve_files(client: &ManageClient, booth_item_id: ItemId, files: &[String]) -> Result<Vec<FileId>, ExecutionError> {
+    let list = client.downloadables(booth_item_id).await?;
+    files.iter().map(|x| find_file(&list.files, x).map(|f| f.id)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixture::uploaded;
+    use crate::variation::find_file;
+
+    #[test]
+    fn find_by_id_or_name() {
+        let files = [uploaded(1, "basic.zip", 10), uploaded(2, "1", 20)];
+
+        assert_eq!(find_file(&files, "basic.zip").unwrap().id, files[0].id);
+        // IDを優先する
+        assert_eq!(find_file(&files, "1").unwrap().id, files[0].id);
+        assert!(find_file(&files, "pro.zip").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use crate::booth::{ItemDraft, ItemExport, ItemId, PublicItem, PublishState, UploadedObject};
+use crate::ExecutionError;
+use crate::manage::ManageClient;
+use crate::message::message;
+use crate::public::PublicClient;
+
+/// デプロイした結果が期待と食い違っている点
+#[derive(Eq, PartialEq, Debug)]
+enum Finding {
+    MissingFile(String),
+    FileSize {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+    UnlinkedFile(String),
+    NotPurchasable(String),
+    NotPublished(PublishState),
+    NotVisible,
+    MissingVariation(String),
+    Mismatch {
+        field: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Self::MissingFile(name) => message!("verify.missing-file", name = name),
+            Self::FileSize { name, expected, actual } => message!("verify.file-size", name = name, expected = expected, actual = actual),
+            Self::UnlinkedFile(name) => message!("verify.unlinked-file", name = name),
+            Self::NotPurchasable(name) => message!("verify.not-purchasable", name = name),
+            Self::NotPublished(state) => message!("verify.not-published", state = state),
+            Self::NotVisible => message!("verify.not-visible"),
+            Self::MissingVariation(name) => message!("verify.missing-variation", variation = name),
+            Self::Mismatch { field, expected, actual } => message!("verify.mismatch", field = field, expected = expected, actual = actual),
+        };
+
+        f.write_str(&message)
+    }
+}
+
+/// 公開ページを`expected`と、管理画面のファイルを`expected_files`の(名前, バイト数)と比べる。バイト数が分からなければ名前だけを見る。
+/// `expected`はデプロイのマニフェストがあればそれ、なければ管理画面の内容。
+fn check(draft: &ItemDraft, expected: &ItemDraft, files: &[UploadedObject], public: Option<&PublicItem>, expected_files: &[(&str, Option<usize>)]) -> Vec<Finding> {
+    let mut findings = vec![];
+    let mut uploaded = vec![];
+    for &(name, size) in expected_files {
+        let Some(file) = files.iter().find(|f| f.name == name) else {
+            findings.push(Finding::MissingFile(name.to_string()));
+            continue
+        };
+
+        if let Some(size) = size.filter(|x| *x != file.file_size) {
+            findings.push(Finding::FileSize { name: name.to_string(), expected: size, actual: file.file_size });
+        }
+        uploaded.push(file);
+    }
+
+    if draft.state != PublishState::Public {
+        findings.push(Finding::NotPublished(draft.state));
+    }
+
+    let Some(public) = public else {
+        findings.push(Finding::NotVisible);
+        return findings
+    };
+
+    // 買えるバリエーションのどれかに紐付いていなければ、買った人はダウンロードできない
+    for file in uploaded {
+        let linked = draft.variations.iter()
+            .filter(|v| v.downloadable_ids.contains(&file.id))
+            .collect::<Vec<_>>();
+        if linked.is_empty() {
+            findings.push(Finding::UnlinkedFile(file.name.clone()));
+            continue
+        }
+
+        // 非公開のバリエーションは公開ページに出ない
+        let purchasable = linked.iter()
+            .any(|v| public.variations.iter().any(|p| v.id == Some(p.id) && p.status != "soldout"));
+        if !purchasable {
+            findings.push(Finding::NotPurchasable(file.name.clone()));
+        }
+    }
+
+    if public.name != expected.name {
+        findings.push(Finding::Mismatch { field: "name".to_string(), expected: expected.name.clone(), actual: public.name.clone() });
+    }
+
+    for variation in &expected.variations {
+        let found = public.variations.iter()
+            .find(|v| variation.id.is_some_and(|id| id == v.id))
+            .or_else(|| public.variations.iter().find(|v| v.name.as_deref() == Some(variation.name.as_str())));
+        let Some(found) = found else {
+            findings.push(Finding::MissingVariation(variation.name.clone()));
+            continue
+        };
+
+        if found.price != variation.price {
+            findings.push(Finding::Mismatch {
+                field: format!("variations[{name}].price", name = variation.name),
+                expected: variation.price.to_string(),
+                actual: found.price.to_string(),
+            });
+        }
+    }
+
+    findings
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn it(client: &ManageClient, booth_item_id: ItemId, expect_files: &[String], manifest: Option<&Path>) -> Result<(), ExecutionError> {
+    let manifest = manifest
+        .map(|path| std::fs::File::open(path).map(std::io::BufReader::new))
+        .transpose()?
+        .map(serde_json::from_reader::<_, ItemExport>)
+        .transpose()?;
+
+    let (form, _) = client.edit_form(booth_item_id).await?;
+    let draft = ItemDraft::from_form(&form)?;
+    let files = client.downloadables(booth_item_id).await?.files;
+    let public = PublicClient::new().item(booth_item_id).await?;
+
+    // 手元にあるファイルなら大きさも確かめ、なければ名前として扱う
+    let local = expect_files.iter()
+        .map(|x| {
+            let path = Path::new(x);
+            let name = path.file_name().map_or_else(|| x.clone(), |x| x.to_string_lossy().to_string());
+            let size = std::fs::metadata(path).ok()
+                .filter(std::fs::Metadata::is_file)
+                .map(|x| usize::try_from(x.len()).unwrap_or(usize::MAX));
+            (name, size)
+        })
+        .collect::<Vec<_>>();
+    let manifest_files = manifest.iter()
+        .flat_map(|x| &x.downloadables)
+        .map(|f| (f.name.as_str(), Some(f.file_size)))
+        .collect::<Vec<_>>();
+    // 名前だけのものがマニフェストにもあれば、マニフェストの大きさで確かめる
+    let mut expected_files = local.iter()
+        .filter(|(name, size)| size.is_some() || !manifest_files.iter().any(|(x, _)| x == name))
+        .map(|(name, size)| (name.as_str(), *size))
+        .collect::<Vec<_>>();
+    expected_files.extend(manifest_files);
+    let expected = manifest.as_ref().map_or(&draft, |x| &x.item);
+
+    let findings = check(&draft, expected, &files, public.as_ref(), &expected_files);
+    for finding in &findings {
+        println!("{}", message!("verify.finding", finding = finding));
+    }
+
+    if findings.is_empty() {
+        println!("{}", message!("verify.ok", item = booth_item_id));
+        Ok(())
+    } else {
+        Err(ExecutionError::VerificationFailed { item: booth_item_id, count: findings.len() })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::booth::{ItemDraft, PublicItem, PublishState, UploadedObject, VariationDraft};
+    use crate::fixture::{self, uploaded, variation};
+    use crate::verify::{check, Finding};
+
+    fn draft(state: PublishState) -> ItemDraft {
+        ItemDraft { state, ..fixture::draft(vec![variation(0, 11, "Basic", 500), variation(1, 12, "Pro", 1500)]) }
+    }
+
+    fn public(pro_price: u32, pro_status: &str) -> PublicItem {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "name": "Sample", "description": "", "price": "¥ 500~", "url": "", "category": null,
+            "variations": [
+                { "id": 11, "name": "Basic", "price": 500, "stock": null, "status": "on_sale", "type": "digital" },
+                { "id": 12, "name": "Pro", "price": pro_price, "stock": null, "status": pro_status, "type": "digital" },
+            ],
+            "shop": { "name": "", "subdomain": "", "url": "" }, "images": [], "tags": [], "wish_lists_count": 0, "published_at": null,
+        })).unwrap()
+    }
+
+    fn files() -> Vec<UploadedObject> {
+        vec![uploaded(1, "app-1.2.0.zip", 100), uploaded(2, "extra.zip", 10)]
+    }
+
+    #[test]
+    fn consistent() {
+        let files = files();
+        let mut draft = draft(PublishState::Public);
+        draft.variations[0].downloadable_ids = vec![files[0].id];
+        assert_eq!(check(&draft, &draft, &files, Some(&public(1500, "on_sale")), &[("app-1.2.0.zip", Some(100))]), []);
+        assert_eq!(check(&draft, &draft, &files, Some(&public(1500, "on_sale")), &[("app-1.2.0.zip", None)]), []);
+    }
+
+    #[test]
+    fn inconsistent() {
+        let draft = draft(PublishState::Private);
+        let mut manifest = draft.clone();
+        manifest.variations.push(VariationDraft { id: None, name: "Ultimate".to_string(), ..manifest.variations[0].clone() });
+
+        assert_eq!(check(&draft, &manifest, &[], Some(&public(2000, "on_sale")), &[("app-1.2.0.zip", Some(100))]), [
+            Finding::MissingFile("app-1.2.0.zip".to_string()),
+            Finding::NotPublished(PublishState::Private),
+            Finding::Mismatch { field: "variations[Pro].price".to_string(), expected: "1500".to_string(), actual: "2000".to_string() },
+            Finding::MissingVariation("Ultimate".to_string()),
+        ]);
+        assert_eq!(check(&draft, &draft, &[], None, &[]), [Finding::NotPublished(PublishState::Private), Finding::NotVisible]);
+    }
+
+    #[test]
+    fn undownloadable_files() {
+        let files = files();
+        let mut draft = draft(PublishState::Public);
+        draft.variations[1].downloadable_ids = vec![files[0].id];
+
+        assert_eq!(check(&draft, &draft, &files, Some(&public(1500, "soldout")), &[("app-1.2.0.zip", Some(99)), ("extra.zip", Some(10))]), [
+            Finding::FileSize { name: "app-1.2.0.zip".to_string(), expected: 99, actual: 100 },
+            Finding::NotPurchasable("app-1.2.0.zip".to_string()),
+            Finding::UnlinkedFile("extra.zip".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::path::Path;
+use serde::Serialize;
+use sqlite3::{Connection, State};
+use crate::booth::{ItemId, ItemRef, PublicItem};
+use crate::ExecutionError;
+use crate::library::LibraryClient;
+use crate::message::message;
+use crate::public::PublicClient;
+use crate::sqlite::{from_sql, read_optional, to_sql, SQLite3ErrorWithCompare};
+
+/// 前回見たときのバリエーションひとつ分
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Snapshot {
+    variation_id: u64,
+    variation: String,
+    price: u32,
+    stock: Option<u32>,
+    status: String,
+}
+
+impl Snapshot {
+    fn of(item: &PublicItem) -> Vec<Self> {
+        item.variations.iter().map(|x| Self {
+            variation_id: x.id,
+            variation: x.name.clone().unwrap_or_default(),
+            price: x.price,
+            stock: x.stock,
+            status: x.status.clone(),
+        }).collect()
+    }
+}
+
+/// `watch prices`が見た価格と在庫を記録するデータベース。最新の状態と、毎回の履歴を持つ
+struct PriceDatabase {
+    connection: Connection,
+}
+
+const SCHEMA: &str = r"
+create table if not exists snapshots (
+    item_id integer not null,
+    variation_id integer not null,
+    variation text not null,
+    price integer not null,
+    stock integer,
+    status text not null,
+    primary key (item_id, variation_id)
+);
+create table if not exists history (
+    item_id integer not null,
+    variation_id integer not null,
+    price integer not null,
+    stock integer,
+    status text not null,
+    checked_at text not null
+);
+";
+
+impl PriceDatabase {
+    fn open(path: &Path) -> Result<Self, SQLite3ErrorWithCompare> {
+        let connection = sqlite3::open(path)?;
+        connection.execute(SCHEMA)?;
+
+        Ok(Self { connection })
+    }
+
+    fn latest(&self, item_id: ItemId) -> Result<Vec<Snapshot>, SQLite3ErrorWithCompare> {
+        let mut statement = self.connection.prepare("select variation_id, variation, price, stock, status from snapshots where item_id = ? order by variation_id")?;
+        statement.bind(1, i64::from(item_id.get()))?;
+        let mut snapshots = vec![];
+        while statement.next()? == State::Row {
+            snapshots.push(Snapshot {
+                variation_id: from_sql(statement.read::<i64>(0)?)?,
+                variation: statement.read::<String>(1)?,
+                price: from_sql(statement.read::<i64>(2)?)?,
+                stock: read_optional(&statement, 3)?,
+                status: statement.read::<String>(4)?,
+            });
+        }
+
+        Ok(snapshots)
+    }
+
+    /// 最新の状態を置き換え、履歴に足す。`snapshots`が空なら最新の状態を消すだけ
+    fn record(&self, item_id: ItemId, snapshots: &[Snapshot]) -> Result<(), SQLite3ErrorWithCompare> {
+        let item_id = i64::from(item_id.get());
+        let checked_at = chrono::Utc::now().to_rfc3339();
+
+        let mut statement = self.connection.prepare("delete from snapshots where item_id = ?")?;
+        statement.bind(1, item_id)?;
+        while statement.next()? == State::Row {}
+
+        for snapshot in snapshots {
+            // 最後の列だけが表によって違う
+            for (sql, last) in [
+                ("insert into snapshots (item_id, variation_id, price, stock, status, variation) values (?, ?, ?, ?, ?, ?)", snapshot.variation.as_str()),
+                ("insert into history (item_id, variation_id, price, stock, status, checked_at) values (?, ?, ?, ?, ?, ?)", checked_at.as_str()),
+            ] {
+                let mut statement = self.connection.prepare(sql)?;
+                statement.bind(1, item_id)?;
+                statement.bind(2, to_sql(snapshot.variation_id)?)?;
+                statement.bind(3, i64::from(snapshot.price))?;
+                match snapshot.stock {
+                    Some(stock) => statement.bind(4, i64::from(stock))?,
+                    None => statement.bind(4, ())?,
+                }
+                statement.bind(5, snapshot.status.as_str())?;
+                statement.bind(6, last)?;
+                while statement.next()? == State::Row {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Clone, Eq, PartialEq, Debug)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Change {
+    PriceDown { from: u32, to: u32 },
+    PriceUp { from: u32, to: u32 },
+    Stock { from: Option<u32>, to: Option<u32> },
+    /// `on_sale`、`soldout`など
+    Status { from: String, to: String },
+    NewVariation { price: u32 },
+    RemovedVariation,
+    /// 公開されなくなった
+    Unavailable,
+}
+
+/// 前回からの変化ひとつ分。通知に流しやすいように、一件を一行で表示する
+#[derive(Serialize, Clone, Eq, PartialEq, Debug)]
+pub struct Event {
+    item_id: ItemId,
+    item_name: String,
+    variation: String,
+    url: String,
+    #[serde(flatten)]
+    change: Change,
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let item = if self.variation.is_empty() { self.item_name.clone() } else { format!("{} / {}", self.item_name, self.variation) };
+        let stock = |x: Option<u32>| x.map_or_else(|| "-".to_string(), |x| x.to_string());
+        let line = match &self.change {
+            Change::PriceDown { from, to } => {
+                let percent = u64::from(from - to) * 100 / u64::from(*from).max(1);
+                message!("watch.price-down", item = item, from = from, to = to, percent = percent, url = self.url)
+            }
+            Change::PriceUp { from, to } => message!("watch.price-up", item = item, from = from, to = to, url = self.url),
+            Change::Stock { from, to } => message!("watch.stock", item = item, from = stock(*from), to = stock(*to), url = self.url),
+            Change::Status { from, to } => message!("watch.status", item = item, from = from, to = to, url = self.url),
+            Change::NewVariation { price } => message!("watch.new-variation", item = item, price = price, url = self.url),
+            Change::RemovedVariation => message!("watch.removed-variation", item = item, url = self.url),
+            Change::Unavailable => message!("watch.unavailable", item = item, url = self.url),
+        };
+
+        f.write_str(&line)
+    }
+}
+
+/// 前回の記録がなければ、比べずに記録するだけ
+fn compare(previous: &[Snapshot], current: &[Snapshot]) -> Vec<(String, Change)> {
+    if previous.is_empty() {
+        return vec![]
+    }
+
+    let mut changes = vec![];
+    for now in current {
+        let Some(before) = previous.iter().find(|x| x.variation_id == now.variation_id) else {
+            changes.push((now.variation.clone(), Change::NewVariation { price: now.price }));
+            continue
+        };
+
+        if now.price < before.price {
+            changes.push((now.variation.clone(), Change::PriceDown { from: before.price, to: now.price }));
+        } else if now.price > before.price {
+            changes.push((now.variation.clone(), Change::PriceUp { from: before.price, to: now.price }));
+        }
+        if now.status != before.status {
+            changes.push((now.variation.clone(), Change::Status { from: before.status.clone(), to: now.status.clone() }));
+        }
+        if now.stock != before.stock {
+            changes.push((now.variation.clone(), Change::Stock { from: before.stock, to: now.stock }));
+        }
+    }
+
+    for before in previous.iter().filter(|x| current.iter().all(|y| y.variation_id != x.variation_id)) {
+        changes.push((before.variation.clone(), Change::RemovedVariation));
+    }
+
+    changes
+}
+
+/// 一行にひとつ、IDかURLを書いたファイル。`-`なら標準入力。最初の空白までを読むので、`wishlist list`の出力をそのまま渡せる
+fn read_items_file(path: &Path) -> Result<Vec<ItemRef>, ExecutionError> {
+    let lines = if path == Path::new("-") {
+        std::io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?
+    } else {
+        std::io::BufReader::new(std::fs::File::open(path)?).lines().collect::<Result<Vec<_>, _>>()?
+    };
+
+    lines.iter()
+        .filter_map(|x| x.split_whitespace().next())
+        .map(|x| x.parse::<ItemRef>().map_err(|e| ExecutionError::CommandLineArgumentValidation(e.to_string())))
+        .collect()
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn wishlist(client: &LibraryClient, json: bool) -> Result<(), ExecutionError> {
+    let items = client.wishlist().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(())
+    }
+
+    for item in &items {
+        let price = item.price.map_or_else(|| "-".to_string(), |x| format!("¥{x}"));
+        println!("{}", message!("wishlist.row", id = item.item_id, name = item.name, shop = item.shop, price = price));
+    }
+
+    Ok(())
+}
+
+/// 公開されている情報を記録し、前回からの変化を一件一行で表示する。変化がなければ何も表示しない。
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) async fn prices(items: &[ItemRef], items_file: Option<&Path>, db: &Path, json: bool) -> Result<(), ExecutionError> {
+    let mut items = items.to_vec();
+    if let Some(path) = items_file {
+        items.extend(read_items_file(path)?);
+    }
+
+    let mut ids = vec![];
+    for item in &items {
+        // 名前から探すにはショップの管理画面が要る
+        let ItemRef::Id(id) = item else {
+            return Err(ExecutionError::CommandLineArgumentValidation(message!("validation.watch-item-by-name")))
+        };
+        if !ids.contains(id) {
+            ids.push(*id);
+        }
+    }
+
+    let database = PriceDatabase::open(db)?;
+    let client = PublicClient::new();
+    eprintln!("{}", message!("watch.checking", count = ids.len()));
+
+    for id in ids {
+        let previous = database.latest(id)?;
+        let (events, current) = match client.item(id).await? {
+            Some(item) => {
+                let current = Snapshot::of(&item);
+                let events = compare(&previous, &current).into_iter()
+                    .map(|(variation, change)| Event { item_id: id, item_name: item.name.clone(), variation, url: item.url.clone(), change })
+                    .collect::<Vec<_>>();
+                (events, current)
+            }
+            None if previous.is_empty() => (vec![], vec![]),
+            None => {
+                let event = Event { item_id: id, item_name: format!("#{id}"), variation: String::new(), url: String::new(), change: Change::Unavailable };
+                (vec![event], vec![])
+            }
+        };
+
+        for event in &events {
+            if json {
+                println!("{}", serde_json::to_string(event)?);
+            } else {
+                println!("{event}");
+            }
+        }
+        database.record(id, &current)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::booth::ItemId;
+    use crate::watch::{compare, Change, PriceDatabase, Snapshot};
+
+    fn snapshot(variation_id: u64, price: u32, stock: Option<u32>, status: &str) -> Snapshot {
+        Snapshot { variation_id, variation: format!("v{variation_id}"), price, stock, status: status.to_string() }
+    }
+
+    #[test]
+    fn changes() {
+        let previous = [snapshot(1, 1000, None, "on_sale"), snapshot(2, 2000, Some(3), "on_sale"), snapshot(3, 500, None, "on_sale")];
+        let current = [snapshot(1, 700, None, "on_sale"), snapshot(2, 2000, Some(0), "soldout"), snapshot(4, 300, None, "on_sale")];
+
+        assert_eq!(compare(&previous, &current), [
+            ("v1".to_string(), Change::PriceDown { from: 1000, to: 700 }),
+            ("v2".to_string(), Change::Status { from: "on_sale".to_string(), to: "soldout".to_string() }),
+            ("v2".to_string(), Change::Stock { from: Some(3), to: Some(0) }),
+            ("v4".to_string(), Change::NewVariation { price: 300 }),
+            ("v3".to_string(), Change::RemovedVariation),
+        ]);
+        assert!(compare(&[], &current).is_empty());
+        assert!(compare(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn database() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = PriceDatabase::open(&dir.path().join("watch.sqlite3")).unwrap();
+        let id = ItemId::new(42).unwrap();
+        let snapshots = [snapshot(1, 1000, None, "on_sale"), snapshot(2, 2000, Some(3), "soldout")];
+
+        assert!(database.latest(id).unwrap().is_empty());
+        database.record(id, &snapshots).unwrap();
+        database.record(id, &snapshots[1..]).unwrap();
+        assert_eq!(database.latest(id).unwrap(), &snapshots[1..]);
+        assert!(database.latest(ItemId::new(43).unwrap()).unwrap().is_empty());
+    }
+}